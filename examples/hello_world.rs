@@ -8,6 +8,17 @@ fn main() {
     App::new()
         .add_plugins((DefaultPlugins, VelloPlugin::default()))
         .add_plugins(VelloGraphicsPlugin)
+        .insert_resource(
+            VelloStyles::new().with_style(
+                "head",
+                VelloStyle::new()
+                    .with_head_fill(Fill::new().with_color(Color::WHITE.with_alpha(0.6)))
+                    .with_head_stroke(
+                        Stroke::from_style(kurbo::Stroke::new(4.0).with_join(kurbo::Join::Miter))
+                            .with_color(Color::BLACK.with_alpha(0.6)),
+                    ),
+            ),
+        )
         .add_systems(Startup, (setup, render_shapes))
         .add_systems(Update, animation)
         .run();
@@ -25,13 +36,7 @@ fn render_shapes(mut commands: Commands) {
     triangle_path.close_path();
 
     let triangle = VelloBezPath::new().with_path(triangle_path);
-    let head_style = (
-        HeadFill(Fill::new().with_color(Color::WHITE.with_alpha(0.6))),
-        HeadStroke(
-            Stroke::from_style(kurbo::Stroke::new(4.0).with_join(kurbo::Join::Miter))
-                .with_color(Color::BLACK.with_alpha(0.6)),
-        ),
-    );
+    let head_style = StyleKey::new("head");
 
     // Line
     let line = (
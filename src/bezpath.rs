@@ -4,8 +4,10 @@ use bevy_ecs::prelude::*;
 use bevy_math::DVec2;
 use bevy_utils::prelude::*;
 use bevy_vello::vello::kurbo;
+use kurbo::ParamCurve;
 
 use super::Vector;
+use crate::measure::{self, NearestPoint, PathMeasure};
 
 /// Vello Bézier path component.
 #[derive(Component, Debug, Clone)]
@@ -30,6 +32,56 @@ impl VelloBezPath {
         self.trace = trace;
         self
     }
+
+    /// Measures the entire path, regardless of the [`trace`][Self::trace].
+    ///
+    /// Prefer this over the other measurement methods when making multiple queries,
+    /// as each of them measures the path again.
+    pub fn measure_path(&self) -> PathMeasure {
+        PathMeasure::new(&self.path)
+    }
+
+    /// Total length of the entire path.
+    pub fn length(&self) -> f64 {
+        self.measure_path().length()
+    }
+
+    /// See [`PathMeasure::point_at_length`].
+    pub fn point_at_length(&self, length: f64) -> Option<DVec2> {
+        self.measure_path()
+            .point_at_length(length)
+            .map(point_to_vec)
+    }
+
+    /// See [`PathMeasure::tangent_at_length`].
+    pub fn tangent_at_length(&self, length: f64) -> Option<DVec2> {
+        self.measure_path()
+            .tangent_at_length(length)
+            .map(|tangent| DVec2::new(tangent.x, tangent.y))
+    }
+
+    /// See [`PathMeasure::normal_at_length`].
+    pub fn normal_at_length(&self, length: f64) -> Option<DVec2> {
+        self.measure_path()
+            .normal_at_length(length)
+            .map(|normal| DVec2::new(normal.x, normal.y))
+    }
+
+    /// See [`PathMeasure::curvature_at_length`].
+    pub fn curvature_at_length(&self, length: f64) -> Option<f64> {
+        self.measure_path().curvature_at_length(length)
+    }
+
+    /// See [`PathMeasure::segment_at_length`].
+    pub fn segment_at_length(&self, length: f64) -> Option<(usize, f64)> {
+        self.measure_path().segment_at_length(length)
+    }
+
+    /// See [`PathMeasure::nearest`].
+    pub fn nearest(&self, point: DVec2) -> Option<NearestPoint> {
+        self.measure_path()
+            .nearest(kurbo::Point::new(point.x, point.y))
+    }
 }
 
 impl Default for VelloBezPath {
@@ -88,10 +140,12 @@ impl Vector for VelloBezPath {
                         } else {
                             path.push(interp_pathel(
                                 most_recent_point,
-                                kurbo::PathEl::MoveTo(most_recent_initial),
+                                kurbo::PathEl::LineTo(most_recent_initial),
                                 interp_value,
                             ));
                         }
+
+                        most_recent_point = most_recent_initial;
                     }
                 }
             }
@@ -101,88 +155,119 @@ impl Vector for VelloBezPath {
     }
 
     fn border_translation(&self, time: f64) -> DVec2 {
-        let pathels = self.path.elements();
-        let pathel_count = pathels.len();
-
-        let fallback = pathels
-            .first()
-            .and_then(|path| path.end_point().map(|point| DVec2::new(point.x, point.y)))
-            .unwrap_or_default();
-
-        // Guarantee to have at least 2 path elements
-        if pathel_count < 2 {
-            return fallback;
-        }
-
-        let seg_count = pathel_count - 1;
-        let trace_raw = time * seg_count as f64;
-        let trace_index = i64::clamp(trace_raw as i64, 0, seg_count as i64 - 1) as usize;
-        let seg_index = trace_index + 1;
-
-        if let Some(segment) = self.path.get_seg(seg_index) {
-            let t = trace_raw - trace_index as f64;
-            return match segment {
-                kurbo::PathSeg::Line(line) => point_to_vec(kurbo::Point::lerp(line.p0, line.p1, t)),
-                kurbo::PathSeg::Quad(kurbo::QuadBez { p0, p1, p2 }) => {
-                    point_to_vec(lerp_quad_point(p0, p1, p2, t))
-                }
-                kurbo::PathSeg::Cubic(kurbo::CubicBez { p0, p1, p2, p3 }) => {
-                    point_to_vec(lerp_cubic_point(p0, p1, p2, p3, t))
-                }
-            };
-        }
-
-        // All else fails..
-        fallback
+        self.trace_tip(time)
+            .map(|(point, _)| point_to_vec(point))
+            .unwrap_or_default()
     }
 
     fn border_rotation(&self, time: f64) -> f64 {
+        self.trace_tip(time)
+            .map(|(_, tangent)| tangent.angle())
+            .unwrap_or_default()
+    }
+}
+
+impl VelloBezPath {
+    /// Point and unit tangent at the tip of the path traced up to `time`.
+    ///
+    /// Follows the same parametrization as [`Vector::shape`], where every path element
+    /// (including [`kurbo::PathEl::MoveTo`] and [`kurbo::PathEl::ClosePath`])
+    /// takes an equal share of the time. The tangent is zero if it cannot be determined.
+    ///
+    /// A `time` outside of `[0.0, 1.0]` extrapolates along the first or last segment,
+    /// so that a head can overshoot the ends of the path.
+    fn trace_tip(&self, time: f64) -> Option<(kurbo::Point, kurbo::Vec2)> {
         let pathels = self.path.elements();
         let pathel_count = pathels.len();
+        if pathel_count == 0 {
+            return None;
+        }
 
-        let fallback = 0.0;
+        // The tip is at the end of the last (partially) traced element.
+        let trace_raw = time * pathel_count as f64;
+        let index = (trace_raw.ceil() as usize).clamp(1, pathel_count) - 1;
+        let t = trace_raw - index as f64;
+        let t = if time > 1.0 { t } else { t.clamp(0.0, 1.0) };
+
+        let mut most_recent_initial = kurbo::Point::ZERO;
+        let mut most_recent_point = kurbo::Point::ZERO;
+        // Most recent segment with a direction, used when the tip has none.
+        let mut prev_tangent = kurbo::Vec2::ZERO;
+
+        for pathel in &pathels[..index] {
+            if let Some(segment) = pathel_segment(most_recent_point, most_recent_initial, *pathel) {
+                if !is_degenerate(segment) {
+                    prev_tangent = measure::tangent(segment, 1.0);
+                }
+            }
 
-        // Guarantee to have at least 2 path elements
-        if pathel_count < 2 {
-            return fallback;
+            match *pathel {
+                kurbo::PathEl::MoveTo(p) => {
+                    most_recent_initial = p;
+                    most_recent_point = p;
+                }
+                kurbo::PathEl::LineTo(p)
+                | kurbo::PathEl::QuadTo(_, p)
+                | kurbo::PathEl::CurveTo(.., p) => most_recent_point = p,
+                kurbo::PathEl::ClosePath => most_recent_point = most_recent_initial,
+            }
         }
 
-        let seg_count = pathel_count - 1;
-        let trace_raw = time * seg_count as f64;
-        let trace_index = i64::clamp(trace_raw as i64, 0, seg_count as i64 - 1) as usize;
-        let seg_index = trace_index + 1;
-
-        if let Some(segment) = self.path.get_seg(seg_index) {
-            let t = trace_raw - trace_index as f64;
-            return match segment {
-                kurbo::PathSeg::Line(line) => (line.p1 - line.p0).angle(),
-                kurbo::PathSeg::Quad(kurbo::QuadBez { p0, p1, p2 }) => {
-                    // kurbo::Point between p0 and p1
-                    let x0 = kurbo::Point::lerp(p0, p1, t);
-                    // kurbo::Point between p1 and p2
-                    let x1 = kurbo::Point::lerp(p1, p2, t);
-                    (x1.y - x0.y).atan2(x1.x - x0.x)
-                }
-                kurbo::PathSeg::Cubic(kurbo::CubicBez { p0, p1, p2, p3 }) => {
-                    // point_to_vec(lerp_cubic_point(cubic.p0, cubic.p1, cubic.p2, cubic.p3, t))
-                    // kurbo::Point between p0 and p1
-                    let x0 = kurbo::Point::lerp(p0, p1, t);
-                    // kurbo::Point between p1 and p2
-                    let x1 = kurbo::Point::lerp(p1, p2, t);
-                    // kurbo::Point between p2 and p3
-                    let x2 = kurbo::Point::lerp(p2, p3, t);
-                    // kurbo::Point between x0 and x1
-                    let y0 = kurbo::Point::lerp(x0, x1, t);
-                    // kurbo::Point between x1 and x2
-                    let y1 = kurbo::Point::lerp(x1, x2, t);
-
-                    (y1.y - y0.y).atan2(y1.x - y0.x)
+        match pathels[index] {
+            // The tip rests at the start of a new subpath, heading towards its first segment.
+            kurbo::PathEl::MoveTo(p) => {
+                let segment = pathels
+                    .get(index + 1)
+                    .and_then(|pathel| pathel_segment(p, p, *pathel))
+                    .filter(|segment| !is_degenerate(*segment));
+                let tangent =
+                    segment.map_or(prev_tangent, |segment| measure::tangent(segment, 0.0));
+
+                // Before the start, the tip moves backwards along the first segment.
+                match segment {
+                    Some(segment) if time < 0.0 => Some((segment.eval(trace_raw), tangent)),
+                    _ => Some((p, tangent)),
                 }
-            };
+            }
+            pathel => {
+                let segment = pathel_segment(most_recent_point, most_recent_initial, pathel)?;
+                let tangent = if is_degenerate(segment) {
+                    prev_tangent
+                } else {
+                    measure::tangent(segment, t)
+                };
+
+                Some((segment.eval(t), tangent))
+            }
         }
+    }
+}
+
+/// Segment drawn by a [`kurbo::PathEl`] from the `current` point,
+/// where `initial` is the start of the current subpath.
+fn pathel_segment(
+    current: kurbo::Point,
+    initial: kurbo::Point,
+    pathel: kurbo::PathEl,
+) -> Option<kurbo::PathSeg> {
+    match pathel {
+        kurbo::PathEl::MoveTo(_) => None,
+        kurbo::PathEl::LineTo(p1) => Some(kurbo::Line::new(current, p1).into()),
+        kurbo::PathEl::QuadTo(p1, p2) => Some(kurbo::QuadBez::new(current, p1, p2).into()),
+        kurbo::PathEl::CurveTo(p1, p2, p3) => {
+            Some(kurbo::CubicBez::new(current, p1, p2, p3).into())
+        }
+        kurbo::PathEl::ClosePath => Some(kurbo::Line::new(current, initial).into()),
+    }
+}
 
-        // All else fails..
-        fallback
+/// Returns true if all points of a `segment` are the same, leaving it without a direction.
+fn is_degenerate(segment: kurbo::PathSeg) -> bool {
+    let p0 = segment.start();
+    match segment {
+        kurbo::PathSeg::Line(line) => line.p1 == p0,
+        kurbo::PathSeg::Quad(quad) => quad.p1 == p0 && quad.p2 == p0,
+        kurbo::PathSeg::Cubic(cubic) => cubic.p1 == p0 && cubic.p2 == p0 && cubic.p3 == p0,
     }
 }
 
@@ -212,15 +297,6 @@ fn lerp_quad_pathel(p0: kurbo::Point, p1: kurbo::Point, p2: kurbo::Point, t: f64
     kurbo::PathEl::QuadTo(x0, end_p)
 }
 
-fn lerp_quad_point(p0: kurbo::Point, p1: kurbo::Point, p2: kurbo::Point, t: f64) -> kurbo::Point {
-    // kurbo::Point between p0 and p1
-    let x0 = kurbo::Point::lerp(p0, p1, t);
-    // kurbo::Point between p1 and p2
-    let x1 = kurbo::Point::lerp(p1, p2, t);
-    // kurbo::Point on curve
-    kurbo::Point::lerp(x0, x1, t)
-}
-
 fn lerp_cubic_pathel(
     p0: kurbo::Point,
     p1: kurbo::Point,
@@ -244,27 +320,194 @@ fn lerp_cubic_pathel(
     kurbo::PathEl::CurveTo(x0, y0, end_p)
 }
 
-fn lerp_cubic_point(
-    p0: kurbo::Point,
-    p1: kurbo::Point,
-    p2: kurbo::Point,
-    p3: kurbo::Point,
-    t: f64,
-) -> kurbo::Point {
-    // kurbo::Point between p0 and p1
-    let x0 = kurbo::Point::lerp(p0, p1, t);
-    // kurbo::Point between p1 and p2
-    let x1 = kurbo::Point::lerp(p1, p2, t);
-    // kurbo::Point between p2 and p3
-    let x2 = kurbo::Point::lerp(p2, p3, t);
-    // kurbo::Point between x0 and x1
-    let y0 = kurbo::Point::lerp(x0, x1, t);
-    // kurbo::Point between x1 and x2
-    let y1 = kurbo::Point::lerp(x1, x2, t);
-    // kurbo::Point on curve
-    kurbo::Point::lerp(y0, y1, t)
-}
-
 fn point_to_vec(point: kurbo::Point) -> DVec2 {
     DVec2::new(point.x, point.y)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_2, PI, TAU};
+
+    use kurbo::Shape;
+
+    use super::*;
+
+    /// Deterministic xorshift generator, so that failures are reproducible.
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: u64) -> u64 {
+            self.next() % n
+        }
+
+        fn unit(&mut self) -> f64 {
+            (self.next() >> 11) as f64 / (1u64 << 53) as f64
+        }
+
+        fn point(&mut self) -> kurbo::Point {
+            kurbo::Point::new(self.unit() * 200.0 - 100.0, self.unit() * 200.0 - 100.0)
+        }
+    }
+
+    /// Random path with multiple subpaths, each optionally closed.
+    fn random_path(rng: &mut XorShift) -> kurbo::BezPath {
+        let mut path = kurbo::BezPath::new();
+
+        for _ in 0..1 + rng.below(3) {
+            path.move_to(rng.point());
+            for _ in 0..1 + rng.below(4) {
+                match rng.below(3) {
+                    0 => path.line_to(rng.point()),
+                    1 => path.quad_to(rng.point(), rng.point()),
+                    _ => path.curve_to(rng.point(), rng.point(), rng.point()),
+                }
+            }
+
+            if rng.below(2) == 0 {
+                path.close_path();
+            }
+        }
+
+        path
+    }
+
+    /// End point and end tangent of the last element of a path.
+    fn path_tip(path: &kurbo::BezPath) -> (kurbo::Point, Option<kurbo::Vec2>) {
+        let mut initial = kurbo::Point::ZERO;
+        let mut current = kurbo::Point::ZERO;
+        let mut tangent = None;
+
+        for pathel in path.elements() {
+            tangent = pathel_segment(current, initial, *pathel)
+                .filter(|segment| !is_degenerate(*segment))
+                .map(|segment| measure::tangent(segment, 1.0));
+
+            match *pathel {
+                kurbo::PathEl::MoveTo(p) => {
+                    initial = p;
+                    current = p;
+                }
+                kurbo::PathEl::LineTo(p)
+                | kurbo::PathEl::QuadTo(_, p)
+                | kurbo::PathEl::CurveTo(.., p) => current = p,
+                kurbo::PathEl::ClosePath => current = initial,
+            }
+        }
+
+        (current, tangent)
+    }
+
+    fn angle_difference(a: f64, b: f64) -> f64 {
+        let difference = (a - b).rem_euclid(TAU);
+        f64::min(difference, TAU - difference)
+    }
+
+    #[test]
+    fn border_follows_traced_shape() {
+        let mut rng = XorShift(0x2545_F491_4F6C_DD1D);
+
+        for _ in 0..500 {
+            let path = random_path(&mut rng);
+            let count = path.elements().len();
+
+            // Element boundaries, including the ends of closing segments, and random times.
+            let times = (1..=count)
+                .map(|i| i as f64 / count as f64)
+                .chain((0..8).map(|_| rng.unit()))
+                .filter(|time| *time > 0.0);
+
+            for time in times {
+                let bez_path = VelloBezPath::new().with_path(path.clone()).with_trace(time);
+                let traced = bez_path.shape().into_path(0.1);
+                let (tip, tangent) = path_tip(&traced);
+
+                let translation = bez_path.border_translation(time);
+                assert!(
+                    translation.distance(point_to_vec(tip)) < 1e-9,
+                    "{time} {path:?}: {translation} != {tip:?}"
+                );
+
+                if let Some(tangent) = tangent {
+                    let rotation = bez_path.border_rotation(time);
+                    assert!(
+                        angle_difference(rotation, tangent.angle()) < 1e-6,
+                        "{time} {path:?}: {rotation} != {}",
+                        tangent.angle()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn times_outside_the_path_extrapolate() {
+        let mut path = kurbo::BezPath::new();
+        path.move_to((0.0, 0.0));
+        path.line_to((10.0, 0.0));
+        path.line_to((10.0, 10.0));
+
+        let bez_path = VelloBezPath::new().with_path(path);
+
+        let translation = bez_path.border_translation(-0.2);
+        assert!(translation.distance(DVec2::new(-6.0, 0.0)) < 1e-9);
+        assert!(angle_difference(bez_path.border_rotation(-0.2), 0.0) < 1e-9);
+
+        let translation = bez_path.border_translation(1.2);
+        assert!(translation.distance(DVec2::new(10.0, 16.0)) < 1e-9);
+        assert!(angle_difference(bez_path.border_rotation(1.2), FRAC_PI_2) < 1e-9);
+    }
+
+    #[test]
+    fn multiple_subpaths() {
+        let mut path = kurbo::BezPath::new();
+        path.move_to((0.0, 0.0));
+        path.line_to((10.0, 0.0));
+        path.line_to((10.0, 10.0));
+        path.close_path();
+        path.move_to((100.0, 0.0));
+        path.line_to((100.0, 10.0));
+
+        let bez_path = VelloBezPath::new().with_path(path);
+
+        // Halfway through the closing segment of the first subpath.
+        let time = 3.5 / 6.0;
+        assert!(
+            bez_path
+                .border_translation(time)
+                .distance(DVec2::new(5.0, 5.0))
+                < 1e-9
+        );
+        assert!(angle_difference(bez_path.border_rotation(time), -PI * 0.75) < 1e-9);
+
+        // At the start of the second subpath, heading towards its first segment.
+        let time = 5.0 / 6.0;
+        assert_eq!(bez_path.border_translation(time), DVec2::new(100.0, 0.0));
+        assert!(angle_difference(bez_path.border_rotation(time), FRAC_PI_2) < 1e-9);
+
+        assert_eq!(bez_path.border_translation(1.0), DVec2::new(100.0, 10.0));
+        assert_eq!(bez_path.border_translation(0.0), DVec2::ZERO);
+    }
+
+    #[test]
+    fn partial_close_path_draws_a_line() {
+        let mut path = kurbo::BezPath::new();
+        path.move_to((0.0, 0.0));
+        path.line_to((10.0, 0.0));
+        path.close_path();
+
+        let bez_path = VelloBezPath::new().with_path(path).with_trace(2.5 / 3.0);
+        let traced = bez_path.shape().into_path(0.1);
+
+        assert_eq!(
+            traced.elements().last(),
+            Some(&kurbo::PathEl::LineTo(kurbo::Point::new(5.0, 0.0)))
+        );
+    }
+}
@@ -0,0 +1,70 @@
+//! Opacity and blending of composited [`VelloScene`]s.
+
+use bevy_ecs::prelude::*;
+use bevy_vello::prelude::*;
+
+/// Opacity of an entity's composited [`VelloScene`].
+///
+/// Unlike the alpha of a [`Brush`][Brush], the opacity is applied to the fill, stroke
+/// and head of a shape together.
+///
+/// [Brush]: crate::brush::Brush
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Opacity(pub f32);
+
+impl Default for Opacity {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// Blend mode of an entity's composited [`VelloScene`] with the content beneath it.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlendMode(pub peniko::BlendMode);
+
+impl Default for BlendMode {
+    fn default() -> Self {
+        Self(peniko::BlendMode::new(
+            peniko::Mix::Normal,
+            peniko::Compose::SrcOver,
+        ))
+    }
+}
+
+impl BlendMode {
+    pub fn new(mix: peniko::Mix, compose: peniko::Compose) -> Self {
+        Self(peniko::BlendMode::new(mix, compose))
+    }
+
+    pub fn from_mix(mix: peniko::Mix) -> Self {
+        Self::new(mix, peniko::Compose::SrcOver)
+    }
+}
+
+/// Bounds of a layer that is expected to cover any shape.
+pub(crate) const LAYER_BOUNDS: kurbo::Rect = kurbo::Rect::new(-1e6, -1e6, 1e6, 1e6);
+
+/// Draw into `scene` inside a blend layer if an [`Opacity`] or [`BlendMode`] is present.
+pub(crate) fn draw_blended(
+    scene: &mut vello::Scene,
+    opacity: Option<&Opacity>,
+    blend_mode: Option<&BlendMode>,
+    draw: impl FnOnce(&mut vello::Scene),
+) {
+    if opacity.is_none() && blend_mode.is_none() {
+        draw(scene);
+        return;
+    }
+
+    let opacity = opacity.copied().unwrap_or_default();
+    let blend_mode = blend_mode.copied().unwrap_or_default();
+
+    scene.push_layer(
+        blend_mode.0,
+        opacity.0,
+        kurbo::Affine::IDENTITY,
+        &LAYER_BOUNDS,
+    );
+    draw(scene);
+    scene.pop_layer();
+}
@@ -0,0 +1,778 @@
+//! Boolean operations between [`Vector`] shapes.
+
+use bevy_ecs::{entity::EntityHashSet, prelude::*};
+use bevy_hierarchy::prelude::*;
+use bevy_transform::prelude::*;
+use bevy_utils::HashMap;
+use bevy_vello::prelude::*;
+use kurbo::Shape;
+
+use crate::{bezpath::VelloBezPath, group::transform_to_affine, vector::TOLERANCE, Vector};
+
+/// Sync the operand paths of [`VelloBoolean`]s that reference [`Vector`] shapes of type `V`.
+#[allow(clippy::type_complexity)]
+pub(super) fn sync_boolean_operands<V: Vector + Component>(
+    mut q_booleans: Query<(Entity, &VelloBoolean, &mut BooleanOperands)>,
+    q_references: Query<&VelloBoolean>,
+    q_vectors: Query<Ref<V>>,
+    q_transforms: Query<(Option<Ref<Transform>>, Option<Ref<Parent>>)>,
+    mut removed_vectors: RemovedComponents<V>,
+) {
+    let removed = removed_vectors.read().collect::<EntityHashSet>();
+
+    for (boolean_entity, boolean, mut operands) in q_booleans.iter_mut() {
+        for (index, entity) in [boolean.a, boolean.b].into_iter().enumerate() {
+            // Operands that depend on the result would never settle.
+            if depends_on(entity, boolean_entity, &q_references) {
+                if operands.0[index].is_some() {
+                    operands.0[index] = None;
+                }
+                continue;
+            }
+
+            let Ok(vector) = q_vectors.get(entity) else {
+                // Forget operands that were despawned or are no longer a vector of this type.
+                if operands.0[index]
+                    .as_ref()
+                    .is_some_and(|(operand, _)| removed.contains(operand))
+                {
+                    operands.0[index] = None;
+                }
+                continue;
+            };
+
+            let is_outdated = operands.0[index]
+                .as_ref()
+                .is_none_or(|(operand, _)| *operand != entity);
+
+            let (transform, is_transform_changed) = compose_transforms(entity, &q_transforms);
+            let (boolean_transform, is_boolean_transform_changed) =
+                compose_transforms(boolean_entity, &q_transforms);
+
+            if !is_outdated
+                && !vector.is_changed()
+                && !is_transform_changed
+                && !is_boolean_transform_changed
+            {
+                continue;
+            }
+
+            // Place the operand relative to the boolean entity, where the result is drawn.
+            let relative = transform.reparented_to(&boolean_transform);
+
+            let mut path = vector.shape().to_path(TOLERANCE);
+            path.apply_affine(transform_to_affine(&relative));
+
+            operands.0[index] = Some((entity, path));
+        }
+    }
+}
+
+/// Composes the [`Transform`]s of an `entity` and its ancestors into the `GlobalTransform`
+/// it has after propagation, and returns whether any of them changed.
+///
+/// Unlike reading the `GlobalTransform`, this does not lag a frame behind transform propagation.
+#[allow(clippy::type_complexity)]
+fn compose_transforms(
+    mut entity: Entity,
+    q_transforms: &Query<(Option<Ref<Transform>>, Option<Ref<Parent>>)>,
+) -> (GlobalTransform, bool) {
+    let mut global = GlobalTransform::IDENTITY;
+    let mut is_changed = false;
+
+    while let Ok((transform, parent)) = q_transforms.get(entity) {
+        if let Some(transform) = transform {
+            global = GlobalTransform::from(*transform) * global;
+            is_changed |= transform.is_changed();
+        }
+
+        let Some(parent) = parent else {
+            break;
+        };
+        is_changed |= parent.is_changed();
+        entity = parent.get();
+    }
+
+    (global, is_changed)
+}
+
+/// Returns true if the `entity` is the `target`,
+/// or a [`VelloBoolean`] with an operand that depends on the `target`.
+fn depends_on(entity: Entity, target: Entity, q_booleans: &Query<&VelloBoolean>) -> bool {
+    let mut visited = EntityHashSet::default();
+    let mut stack = vec![entity];
+
+    while let Some(entity) = stack.pop() {
+        if entity == target {
+            return true;
+        }
+
+        if visited.insert(entity) {
+            if let Ok(boolean) = q_booleans.get(entity) {
+                stack.extend([boolean.a, boolean.b]);
+            }
+        }
+    }
+
+    false
+}
+
+/// Evaluate [`VelloBoolean`]s whose operation or operands have changed.
+pub(super) fn evaluate_booleans(mut q_booleans: Query<(&mut VelloBoolean, Ref<BooleanOperands>)>) {
+    for (mut boolean, operands) in q_booleans.iter_mut() {
+        if !boolean.is_changed() && !operands.is_changed() {
+            continue;
+        }
+
+        // Operands that are no longer referenced are treated as empty.
+        let [a, b] = [(0, boolean.a), (1, boolean.b)].map(|(index, entity)| {
+            operands.0[index]
+                .as_ref()
+                .filter(|(operand, _)| *operand == entity)
+                .map(|(_, path)| path.clone())
+                .unwrap_or_default()
+        });
+
+        boolean.path = boolean_path(&a, &b, boolean.op);
+    }
+}
+
+/// Boolean operation between two shapes.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BooleanOp {
+    /// Area covered by either shape.
+    #[default]
+    Union,
+    /// Area covered by both shapes.
+    Intersect,
+    /// Area of the first shape that is not covered by the second shape.
+    Difference,
+    /// Area covered by exactly one of the shapes.
+    Xor,
+}
+
+impl BooleanOp {
+    /// Returns true if a point with the given coverage is part of the result.
+    fn contains(self, in_a: bool, in_b: bool) -> bool {
+        match self {
+            BooleanOp::Union => in_a || in_b,
+            BooleanOp::Intersect => in_a && in_b,
+            BooleanOp::Difference => in_a && !in_b,
+            BooleanOp::Xor => in_a != in_b,
+        }
+    }
+}
+
+/// A [`Vector`] that is the result of a [`BooleanOp`] between two entities with [`Vector`]s.
+///
+/// The operands are placed by their [`Transform`]s relative to the [`Transform`] of this entity
+/// (both composed through the hierarchy), so the result lines up with the operands wherever they
+/// are in the hierarchy, without waiting for transform propagation.
+/// The result is re-evaluated whenever the operands, their placement or the operation change.
+/// An operand that is despawned or no longer has a [`Vector`] is treated as empty, and so is an
+/// operand that is this entity itself or another [`VelloBoolean`] that depends on it.
+/// Curves are flattened, so the resulting path only consists of lines.
+#[derive(Component, Debug, Clone)]
+#[require(BooleanOperands)]
+pub struct VelloBoolean {
+    pub a: Entity,
+    pub b: Entity,
+    pub op: BooleanOp,
+    path: kurbo::BezPath,
+}
+
+impl VelloBoolean {
+    pub fn new(a: Entity, b: Entity, op: BooleanOp) -> Self {
+        Self {
+            a,
+            b,
+            op,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_op(mut self, op: BooleanOp) -> Self {
+        self.op = op;
+        self
+    }
+
+    /// Returns the evaluated path.
+    pub fn path(&self) -> &kurbo::BezPath {
+        &self.path
+    }
+}
+
+impl Default for VelloBoolean {
+    fn default() -> Self {
+        Self {
+            a: Entity::PLACEHOLDER,
+            b: Entity::PLACEHOLDER,
+            op: BooleanOp::default(),
+            path: kurbo::BezPath::new(),
+        }
+    }
+}
+
+impl Vector for VelloBoolean {
+    fn shape(&self) -> impl kurbo::Shape {
+        self.path.clone()
+    }
+}
+
+/// Cached paths of the operands of a [`VelloBoolean`].
+#[derive(Component, Default, Debug, Clone)]
+pub struct BooleanOperands([Option<(Entity, kurbo::BezPath)>; 2]);
+
+/// Performs a [`BooleanOp`] between two [`Vector`] shapes.
+pub fn boolean(a: &impl Vector, b: &impl Vector, op: BooleanOp) -> VelloBezPath {
+    VelloBezPath::new().with_path(boolean_path(&a.shape(), &b.shape(), op))
+}
+
+/// Performs a [`BooleanOp`] between two shapes filled with [`peniko::Fill::NonZero`].
+///
+/// Both shapes are flattened into polygons, so the resulting path only consists of lines.
+/// Subpaths of the result are wound so that holes have the opposite direction of their outline,
+/// making the result independent of the fill rule.
+///
+/// Intersections are only tested between edges with overlapping bounds, and the resulting edges
+/// are only tested against the edges of the operands at the same height, so flattened curves
+/// with many short edges stay cheap to combine.
+pub fn boolean_path(a: &impl Shape, b: &impl Shape, op: BooleanOp) -> kurbo::BezPath {
+    let (polygon_a, polygon_b) = (flatten_polygon(a), flatten_polygon(b));
+
+    let bounds = polygon_a.bounding_box().union(polygon_b.bounding_box());
+    let size = (bounds.width() + bounds.height()).max(1.0);
+    // A power of two grid keeps coordinates that are already on the grid exact.
+    let snap = Snap((size.log2().ceil() - 30.0).exp2());
+    // Distance to sample on either side of an edge, larger than the snapping error.
+    let epsilon = snap.0 * 4.0;
+
+    let (polygon_a, polygon_b) = (snap.path(&polygon_a), snap.path(&polygon_b));
+
+    let (edges_a, edges_b) = (polygon_edges(&polygon_a), polygon_edges(&polygon_b));
+    let (bands_a, bands_b) = (WindingBands::new(&edges_a), WindingBands::new(&edges_b));
+    let edges = [edges_a, edges_b].concat();
+
+    let mut pieces = Vec::<(kurbo::Point, kurbo::Point)>::new();
+    for (p0, p1) in split_edges(&edges, snap) {
+        let delta = p1 - p0;
+        let length = delta.hypot();
+        if length <= 0.0 {
+            continue;
+        }
+
+        let mid = p0.midpoint(p1);
+        let normal = kurbo::Vec2::new(-delta.y, delta.x) * (epsilon / length);
+
+        let contains =
+            |point: kurbo::Point| op.contains(bands_a.contains(point), bands_b.contains(point));
+
+        // Keep the edges at the border of the result, with the result on their left side.
+        match (contains(mid + normal), contains(mid - normal)) {
+            (true, false) => pieces.push((p0, p1)),
+            (false, true) => pieces.push((p1, p0)),
+            _ => {}
+        }
+    }
+
+    connect_pieces(pieces)
+}
+
+/// Rounds points to a grid, so that nearly coincident points become identical.
+#[derive(Clone, Copy)]
+struct Snap(f64);
+
+impl Snap {
+    fn point(self, point: kurbo::Point) -> kurbo::Point {
+        // Adding zero turns negative zeros into positive zeros, as points are compared by bits.
+        kurbo::Point::new(
+            (point.x / self.0).round() * self.0 + 0.0,
+            (point.y / self.0).round() * self.0 + 0.0,
+        )
+    }
+
+    fn path(self, path: &kurbo::BezPath) -> kurbo::BezPath {
+        path.iter()
+            .map(|el| match el {
+                kurbo::PathEl::MoveTo(p) => kurbo::PathEl::MoveTo(self.point(p)),
+                kurbo::PathEl::LineTo(p) => kurbo::PathEl::LineTo(self.point(p)),
+                el => el,
+            })
+            .collect()
+    }
+}
+
+/// Flatten a shape into a polygon path, closing all subpaths.
+fn flatten_polygon(shape: &impl Shape) -> kurbo::BezPath {
+    let mut polygon = kurbo::BezPath::new();
+    kurbo::flatten(shape.path_elements(TOLERANCE), TOLERANCE, |el| {
+        polygon.push(el)
+    });
+
+    close_subpaths(polygon)
+}
+
+/// Collect path elements into a path where every subpath is closed, as they are when filled.
+pub(crate) fn close_subpaths(elements: impl IntoIterator<Item = kurbo::PathEl>) -> kurbo::BezPath {
+    let mut path = kurbo::BezPath::new();
+    let mut is_open = false;
+
+    for el in elements {
+        match el {
+            kurbo::PathEl::MoveTo(_) if is_open => path.close_path(),
+            kurbo::PathEl::ClosePath if !is_open => continue,
+            _ => {}
+        }
+
+        is_open = !matches!(el, kurbo::PathEl::ClosePath);
+        path.push(el);
+    }
+
+    if is_open {
+        path.close_path();
+    }
+
+    path
+}
+
+/// Edges of a polygon path, including the closing edges.
+fn polygon_edges(polygon: &kurbo::BezPath) -> Vec<(kurbo::Point, kurbo::Point)> {
+    polygon
+        .segments()
+        .filter_map(|seg| match seg {
+            kurbo::PathSeg::Line(line) if line.p0 != line.p1 => Some((line.p0, line.p1)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Edges of a polygon sorted into horizontal bands, so that the winding number of a point
+/// only needs the edges crossing its band.
+struct WindingBands {
+    y0: f64,
+    height: f64,
+    bands: Vec<Vec<(kurbo::Point, kurbo::Point)>>,
+}
+
+impl WindingBands {
+    fn new(edges: &[(kurbo::Point, kurbo::Point)]) -> Self {
+        let (y0, y1) = edges
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(y0, y1), (p0, p1)| {
+                (y0.min(p0.y).min(p1.y), y1.max(p0.y).max(p1.y))
+            });
+
+        let mut bands = Self {
+            y0,
+            height: 0.0,
+            bands: Vec::new(),
+        };
+        if edges.is_empty() {
+            return bands;
+        }
+
+        // Balance the number of bands against the number of edges in each band.
+        let count = (edges.len() as f64).sqrt().ceil();
+        bands.height = ((y1 - y0) / count).max(f64::MIN_POSITIVE);
+        bands.bands = vec![Vec::new(); count as usize];
+
+        for &(p0, p1) in edges {
+            for band in bands.band(p0.y.min(p1.y))..=bands.band(p0.y.max(p1.y)) {
+                bands.bands[band].push((p0, p1));
+            }
+        }
+
+        bands
+    }
+
+    /// Index of the band containing the height `y`, clamped to the existing bands.
+    fn band(&self, y: f64) -> usize {
+        let band = ((y - self.y0) / self.height).floor().max(0.0) as usize;
+        band.min(self.bands.len() - 1)
+    }
+
+    /// Returns true if the `point` is inside the polygon filled with [`peniko::Fill::NonZero`].
+    fn contains(&self, point: kurbo::Point) -> bool {
+        if self.bands.is_empty() {
+            return false;
+        }
+
+        let mut winding = 0;
+        for &(p0, p1) in &self.bands[self.band(point.y)] {
+            // Count the edges crossing a ray from the point to the right.
+            if (p0.y <= point.y) != (p1.y <= point.y) {
+                let x = p0.x + (point.y - p0.y) / (p1.y - p0.y) * (p1.x - p0.x);
+                if x > point.x {
+                    winding += if p1.y > p0.y { 1 } else { -1 };
+                }
+            }
+        }
+
+        winding != 0
+    }
+}
+
+/// Split edges at all of their intersections with each other.
+///
+/// Intersection points are snapped and shared between the split edges,
+/// so that they can be connected by exact comparison.
+/// Only pairs of edges with overlapping bounds are tested, found by sweeping over the edges
+/// from left to right, so flattened curves with many short edges stay cheap to split.
+fn split_edges(
+    edges: &[(kurbo::Point, kurbo::Point)],
+    snap: Snap,
+) -> Vec<(kurbo::Point, kurbo::Point)> {
+    let mut splits = vec![Vec::<(f64, kurbo::Point)>::new(); edges.len()];
+
+    let bounds = edges
+        .iter()
+        .map(|&(p0, p1)| kurbo::Rect::from_points(p0, p1))
+        .collect::<Vec<_>>();
+    let mut sweep = (0..edges.len()).collect::<Vec<_>>();
+    sweep.sort_by(|&a, &b| bounds[a].x0.total_cmp(&bounds[b].x0));
+
+    for (k, &i) in sweep.iter().enumerate() {
+        let (p0, p1) = edges[i];
+
+        for &j in &sweep[k + 1..] {
+            // Edges further along the sweep start to the right of this edge.
+            if bounds[j].x0 > bounds[i].x1 {
+                break;
+            }
+            if !overlaps(bounds[i], bounds[j]) {
+                continue;
+            }
+
+            let (q0, q1) = edges[j];
+
+            let r = p1 - p0;
+            let s = q1 - q0;
+            let qp = q0 - p0;
+            let denom = r.cross(s);
+
+            if denom.abs() > 1e-12 * r.hypot() * s.hypot() {
+                let t = qp.cross(s) / denom;
+                let u = qp.cross(r) / denom;
+                if !(0.0..=1.0).contains(&t) || !(0.0..=1.0).contains(&u) {
+                    continue;
+                }
+
+                // Snap to existing end points to keep the edges connected.
+                let point = if t == 0.0 {
+                    p0
+                } else if t == 1.0 {
+                    p1
+                } else if u == 0.0 {
+                    q0
+                } else if u == 1.0 {
+                    q1
+                } else {
+                    snap.point(p0.lerp(p1, t))
+                };
+
+                splits[i].push((t, point));
+                splits[j].push((u, point));
+            } else if qp.cross(r).abs() <= 1e-12 * r.hypot2().max(s.hypot2()) {
+                // Collinear edges split each other at their end points.
+                for q in [q0, q1] {
+                    splits[i].push(((q - p0).dot(r) / r.hypot2(), q));
+                }
+                for p in [p0, p1] {
+                    splits[j].push(((p - q0).dot(s) / s.hypot2(), p));
+                }
+            }
+        }
+    }
+
+    let mut pieces = Vec::with_capacity(edges.len());
+    for (&(p0, p1), mut splits) in edges.iter().zip(splits) {
+        splits.retain(|(t, _)| *t > 0.0 && *t < 1.0);
+        splits.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut start = p0;
+        for (_, point) in splits {
+            if point != start {
+                pieces.push((start, point));
+                start = point;
+            }
+        }
+        if start != p1 {
+            pieces.push((start, p1));
+        }
+    }
+
+    pieces
+}
+
+/// Returns true if two rects overlap, including their borders.
+fn overlaps(a: kurbo::Rect, b: kurbo::Rect) -> bool {
+    a.x0 <= b.x1 && b.x0 <= a.x1 && a.y0 <= b.y1 && b.y0 <= a.y1
+}
+
+/// Connect directed pieces into closed subpaths.
+fn connect_pieces(pieces: Vec<(kurbo::Point, kurbo::Point)>) -> kurbo::BezPath {
+    let key = |point: kurbo::Point| (point.x.to_bits(), point.y.to_bits());
+
+    // Remove duplicated pieces from overlapping edges.
+    let mut unique = HashMap::<_, (kurbo::Point, kurbo::Point)>::default();
+    for (p0, p1) in pieces {
+        unique.entry((key(p0), key(p1))).or_insert((p0, p1));
+    }
+    let mut pieces = unique.into_values().collect::<Vec<_>>();
+    // Keep the output deterministic.
+    pieces.sort_by(|a, b| {
+        (a.0.x, a.0.y, a.1.x, a.1.y)
+            .partial_cmp(&(b.0.x, b.0.y, b.1.x, b.1.y))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let mut outgoing = HashMap::<_, Vec<usize>>::default();
+    for (index, (p0, _)) in pieces.iter().enumerate() {
+        outgoing.entry(key(*p0)).or_default().push(index);
+    }
+
+    let mut used = vec![false; pieces.len()];
+    let mut path = kurbo::BezPath::new();
+
+    for first in 0..pieces.len() {
+        if used[first] {
+            continue;
+        }
+
+        let mut points = vec![pieces[first].0];
+        let mut current = first;
+        loop {
+            used[current] = true;
+            let end = pieces[current].1;
+
+            let next = outgoing
+                .get(&key(end))
+                .and_then(|indices| indices.iter().copied().find(|&index| !used[index]));
+
+            match next {
+                Some(next) => {
+                    points.push(end);
+                    current = next;
+                }
+                // Either the loop is closed, or the remaining pieces are dangling.
+                None => break,
+            }
+        }
+
+        remove_collinear(&mut points);
+        if points.len() < 3 {
+            continue;
+        }
+
+        path.move_to(points[0]);
+        for point in &points[1..] {
+            path.line_to(*point);
+        }
+        path.close_path();
+    }
+
+    path
+}
+
+/// Remove points of a closed polygon that lie on the line between their neighbors.
+fn remove_collinear(points: &mut Vec<kurbo::Point>) {
+    let mut index = 0;
+    while points.len() >= 3 && index < points.len() {
+        let count = points.len();
+        let prev = points[(index + count - 1) % count];
+        let next = points[(index + 1) % count];
+        let point = points[index];
+
+        let is_collinear = (point - prev).cross(next - point).abs()
+            <= 1e-12 * (point - prev).hypot2().max((next - point).hypot2());
+        let is_forward = (point - prev).dot(next - point) >= 0.0;
+
+        if is_collinear && is_forward {
+            points.remove(index);
+        } else {
+            index += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hit::fill_contains;
+
+    const OPS: [BooleanOp; 4] = [
+        BooleanOp::Union,
+        BooleanOp::Intersect,
+        BooleanOp::Difference,
+        BooleanOp::Xor,
+    ];
+
+    /// Signed areas of each subpath of a `path`.
+    fn subpath_areas(path: &kurbo::BezPath) -> Vec<f64> {
+        let mut areas = Vec::new();
+        let mut subpath = kurbo::BezPath::new();
+
+        for el in path.iter() {
+            if matches!(el, kurbo::PathEl::MoveTo(_)) && !subpath.elements().is_empty() {
+                areas.push(subpath.area());
+                subpath = kurbo::BezPath::new();
+            }
+            subpath.push(el);
+        }
+        if !subpath.elements().is_empty() {
+            areas.push(subpath.area());
+        }
+
+        areas
+    }
+
+    /// Asserts the area and number of subpaths of the result of each [`BooleanOp`],
+    /// and that the result covers the same points as the operation on its operands.
+    fn assert_ops(a: &impl Shape, b: &impl Shape, expected: [(f64, usize); 4]) {
+        let bounds = a.bounding_box().union(b.bounding_box());
+
+        for (op, (area, subpaths)) in OPS.into_iter().zip(expected) {
+            let path = boolean_path(a, b, op);
+
+            assert!(
+                (path.area().abs() - area).abs() < 1e-9,
+                "{op:?}: area {} != {area}",
+                path.area()
+            );
+            assert_eq!(subpath_areas(&path).len(), subpaths, "{op:?}: {path:?}");
+
+            // Sample the centers of a grid, avoiding the edges of the operands.
+            for y in 0..20 {
+                for x in 0..20 {
+                    let point = kurbo::Point::new(
+                        bounds.x0 + bounds.width() * (x as f64 + 0.5) / 20.0,
+                        bounds.y0 + bounds.height() * (y as f64 + 0.5) / 20.0,
+                    );
+                    let expected = op.contains(
+                        fill_contains(a, point, peniko::Fill::NonZero),
+                        fill_contains(b, point, peniko::Fill::NonZero),
+                    );
+
+                    // The result must be the same under both fill rules.
+                    for fill_rule in [peniko::Fill::NonZero, peniko::Fill::EvenOdd] {
+                        assert_eq!(
+                            fill_contains(&path, point, fill_rule),
+                            expected,
+                            "{op:?} at {point:?} with {fill_rule:?}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn overlapping_rects() {
+        let a = kurbo::Rect::new(0.0, 0.0, 10.0, 10.0);
+        let b = kurbo::Rect::new(5.0, 5.0, 15.0, 15.0);
+
+        assert_ops(&a, &b, [(175.0, 1), (25.0, 1), (75.0, 1), (150.0, 2)]);
+    }
+
+    #[test]
+    fn winding_matches_operands() {
+        let a = kurbo::Rect::new(0.0, 0.0, 10.0, 10.0);
+        let b = kurbo::Rect::new(5.0, 5.0, 15.0, 15.0);
+        let sign = a.to_path(TOLERANCE).area().signum();
+
+        for op in OPS {
+            for area in subpath_areas(&boolean_path(&a, &b, op)) {
+                assert_eq!(area.signum(), sign, "{op:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn touching_along_an_edge() {
+        let a = kurbo::Rect::new(0.0, 0.0, 10.0, 10.0);
+        let b = kurbo::Rect::new(10.0, 0.0, 20.0, 10.0);
+
+        assert_ops(&a, &b, [(200.0, 1), (0.0, 0), (100.0, 1), (200.0, 1)]);
+
+        // The shared edge is removed, leaving a single rectangle.
+        let union = boolean_path(&a, &b, BooleanOp::Union);
+        assert_eq!(union.elements().len(), 5);
+    }
+
+    #[test]
+    fn touching_at_a_corner() {
+        let a = kurbo::Rect::new(0.0, 0.0, 10.0, 10.0);
+        let b = kurbo::Rect::new(10.0, 10.0, 20.0, 20.0);
+
+        assert_ops(&a, &b, [(200.0, 2), (0.0, 0), (100.0, 1), (200.0, 2)]);
+    }
+
+    #[test]
+    fn collinear_overlapping_edges() {
+        let a = kurbo::Rect::new(0.0, 0.0, 10.0, 10.0);
+        let b = kurbo::Rect::new(5.0, 0.0, 15.0, 10.0);
+
+        assert_ops(&a, &b, [(150.0, 1), (50.0, 1), (50.0, 1), (100.0, 2)]);
+    }
+
+    #[test]
+    fn identical_shapes() {
+        let a = kurbo::Rect::new(0.0, 0.0, 10.0, 10.0);
+
+        assert_ops(&a, &a, [(100.0, 1), (100.0, 1), (0.0, 0), (0.0, 0)]);
+    }
+
+    #[test]
+    fn operand_with_a_hole() {
+        // A square ring, with the hole wound against the outline.
+        let mut ring = kurbo::Rect::new(0.0, 0.0, 20.0, 20.0).to_path(TOLERANCE);
+        ring.extend(
+            kurbo::Rect::new(5.0, 5.0, 15.0, 15.0)
+                .to_path(TOLERANCE)
+                .reverse_subpaths(),
+        );
+        let bar = kurbo::Rect::new(0.0, 8.0, 20.0, 12.0);
+
+        assert_ops(&ring, &bar, [(340.0, 3), (40.0, 2), (260.0, 2), (300.0, 2)]);
+
+        // Holes are wound against their outline.
+        let areas = subpath_areas(&boolean_path(&ring, &bar, BooleanOp::Union));
+        assert_eq!(
+            areas.iter().filter(|area| area.is_sign_negative()).count(),
+            2
+        );
+    }
+
+    #[test]
+    fn curves_are_flattened() {
+        let a = kurbo::Circle::new((0.0, 0.0), 10.0);
+        let b = kurbo::Circle::new((10.0, 0.0), 10.0);
+
+        let union = boolean_path(&a, &b, BooleanOp::Union);
+        let intersect = boolean_path(&a, &b, BooleanOp::Intersect);
+        assert!(union
+            .iter()
+            .all(|el| !matches!(el, kurbo::PathEl::QuadTo(..) | kurbo::PathEl::CurveTo(..))));
+
+        // Inclusion-exclusion holds up to the flattening tolerance.
+        let total = union.area().abs() + intersect.area().abs();
+        assert!((total - a.area() - b.area()).abs() < 10.0);
+    }
+
+    #[test]
+    fn large_curves() {
+        // Finely flattened circles with many short edges.
+        let radius = 1e4;
+        let a = kurbo::Circle::new((0.0, 0.0), radius);
+        let b = kurbo::Circle::new((radius, 0.0), radius);
+        assert!(flatten_polygon(&a).elements().len() > 500);
+
+        // Area of the lens where circles at a distance of their radius overlap.
+        let lens = radius * radius * (2.0 * std::f64::consts::FRAC_PI_3 - 3f64.sqrt() * 0.5);
+
+        let union = boolean_path(&a, &b, BooleanOp::Union).area().abs();
+        let intersect = boolean_path(&a, &b, BooleanOp::Intersect).area().abs();
+        assert!((union / (a.area() + b.area() - lens) - 1.0).abs() < 1e-3);
+        assert!((intersect / lens - 1.0).abs() < 1e-3);
+    }
+}
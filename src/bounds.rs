@@ -0,0 +1,140 @@
+//! Bounds of [`Vector`] shapes including their strokes and heads.
+
+use bevy_ecs::prelude::*;
+use bevy_math::{bounding::Aabb2d, Vec2};
+use bevy_vello::vello::kurbo;
+
+use crate::{
+    head::{HeadStroke, HeadStrokes, HeadTransform, HeadVector},
+    stroke::Strokes,
+    variable_stroke::VariableStroke,
+    Stroke, Vector,
+};
+
+/// Compute the [`VectorBounds`] of [`Vector`] shapes.
+#[allow(clippy::type_complexity)]
+pub(super) fn compute_bounds<V: Vector + Component>(
+    mut commands: Commands,
+    mut q_vectors: Query<
+        (
+            Entity,
+            &V,
+            Option<&Stroke>,
+            Option<&Strokes>,
+            Option<&VariableStroke>,
+            Option<&mut VectorBounds>,
+        ),
+        Or<(
+            Changed<V>,
+            Changed<Stroke>,
+            Changed<Strokes>,
+            Changed<VariableStroke>,
+        )>,
+    >,
+) {
+    for (entity, vector, stroke, strokes, variable_stroke, bounds) in q_vectors.iter_mut() {
+        let outline = vector.shape();
+        let extent = stroke
+            .into_iter()
+            .chain(strokes.iter().flat_map(|strokes| &strokes.0))
+            .map(|stroke| stroke.extent(&outline))
+            .chain(variable_stroke.map(VariableStroke::extent))
+            .fold(0.0, f64::max);
+
+        let shape = vector.bounds().inflate(extent, extent);
+
+        match bounds {
+            Some(mut bounds) => bounds.shape = shape,
+            None => {
+                commands
+                    .entity(entity)
+                    .insert(VectorBounds { shape, head: None });
+            }
+        }
+    }
+}
+
+/// Compute the bounds of [`HeadVector`]s placed by their [`HeadTransform`] into [`VectorBounds`].
+#[allow(clippy::type_complexity)]
+pub(super) fn compute_head_bounds<V: Vector + Component>(
+    mut commands: Commands,
+    mut q_heads: Query<
+        (
+            Entity,
+            &HeadVector<V>,
+            &HeadTransform,
+            Option<&HeadStroke>,
+            Option<&HeadStrokes>,
+            Option<&mut VectorBounds>,
+        ),
+        Or<(
+            Changed<HeadVector<V>>,
+            Changed<HeadTransform>,
+            Changed<HeadStroke>,
+            Changed<HeadStrokes>,
+        )>,
+    >,
+) {
+    for (entity, head_vector, head_transform, stroke, strokes, bounds) in q_heads.iter_mut() {
+        let outline = head_vector.0.shape();
+        let extent = stroke
+            .map(|stroke| &stroke.0)
+            .into_iter()
+            .chain(strokes.iter().flat_map(|strokes| &strokes.0 .0))
+            .map(|stroke| stroke.extent(&outline))
+            .fold(0.0, f64::max);
+
+        let head = head_transform
+            .affine()
+            .transform_rect_bbox(head_vector.0.bounds().inflate(extent, extent));
+
+        match bounds {
+            Some(mut bounds) => bounds.head = Some(head),
+            None => {
+                commands.entity(entity).insert(VectorBounds {
+                    shape: kurbo::Rect::ZERO,
+                    head: Some(head),
+                });
+            }
+        }
+    }
+}
+
+/// Local bounds of a [`Vector`], including its strokes and head.
+///
+/// Bounds are computed in the coordinate space of the shape, before the entity's `Transform`.
+#[derive(Component, Default, Debug, Clone, Copy, PartialEq)]
+pub struct VectorBounds {
+    shape: kurbo::Rect,
+    head: Option<kurbo::Rect>,
+}
+
+impl VectorBounds {
+    /// Bounds of the shape and its strokes, excluding the head.
+    pub fn shape(&self) -> kurbo::Rect {
+        self.shape
+    }
+
+    /// Bounds of the head and its strokes, if any.
+    pub fn head(&self) -> Option<kurbo::Rect> {
+        self.head
+    }
+
+    /// Bounds of the shape and its head.
+    pub fn rect(&self) -> kurbo::Rect {
+        match self.head {
+            Some(head) => self.shape.union(head),
+            None => self.shape,
+        }
+    }
+
+    /// Bounds of the shape and its head as an [`Aabb2d`] in Bevy's y-up coordinates.
+    pub fn aabb2d(&self) -> Aabb2d {
+        let rect = kurbo::Affine::FLIP_Y.transform_rect_bbox(self.rect());
+
+        Aabb2d {
+            min: Vec2::new(rect.x0 as f32, rect.y0 as f32),
+            max: Vec2::new(rect.x1 as f32, rect.y1 as f32),
+        }
+    }
+}
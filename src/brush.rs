@@ -1,13 +1,104 @@
 //! A Bevy friendly wrapper around [`peniko::Brush`].
 
-use bevy_color::Color;
-use bevy_utils::prelude::*;
+use bevy_asset::prelude::*;
+use bevy_color::{Color, ColorToComponents, LinearRgba, Mix, Oklaba, Srgba};
+use bevy_ecs::prelude::*;
+use bevy_image::{BevyDefault, Image};
+use bevy_math::DVec2;
+use bevy_utils::{hashbrown::hash_map::Entry, prelude::*, HashMap, HashSet};
 use bevy_vello::prelude::*;
 
+/// Invalidate the [`ImageBrushCache`] when [`Image`] assets are loaded or modified.
+pub(super) fn update_image_brush_cache(
+    mut events: EventReader<AssetEvent<Image>>,
+    mut cache: ResMut<ImageBrushCache>,
+) {
+    cache.updated.clear();
+
+    for event in events.read() {
+        match *event {
+            AssetEvent::Added { id }
+            | AssetEvent::Modified { id }
+            | AssetEvent::LoadedWithDependencies { id } => {
+                cache.images.remove(&id);
+                cache.updated.insert(id);
+            }
+            AssetEvent::Removed { id } | AssetEvent::Unused { id } => {
+                cache.images.remove(&id);
+            }
+        }
+    }
+}
+
+/// Resolve [`ImageBrush`]es of a [`BrushHolder`] into [`peniko::Brush::Image`]s.
+pub(super) fn sync_image_brushes<T: BrushHolder + Component>(
+    mut q_holders: Query<&mut T>,
+    images: Res<Assets<Image>>,
+    mut cache: ResMut<ImageBrushCache>,
+) {
+    for mut holder in q_holders.iter_mut() {
+        let updated = !cache.updated.is_empty()
+            && holder.brushes().any(|brush| {
+                brush
+                    .image
+                    .as_ref()
+                    .is_some_and(|image| cache.updated.contains(&image.handle.id()))
+            });
+
+        if !holder.is_changed() && !updated {
+            continue;
+        }
+
+        // Brushes fall back to the transparent placeholder while their image is not loaded,
+        // so that a stale image does not linger after the handle changes.
+        let values = holder
+            .brushes()
+            .map(|brush| {
+                brush.image.as_ref().map(|image| {
+                    cache
+                        .resolve(&images, image)
+                        .map(peniko::Brush::Image)
+                        .unwrap_or_default()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        // Only mutate when needed to prevent triggering change detection on every frame.
+        let outdated = holder
+            .brushes()
+            .zip(&values)
+            .any(|(brush, value)| value.as_ref().is_some_and(|value| brush.value != *value));
+
+        if outdated {
+            for (brush, value) in holder.brushes_mut().zip(values) {
+                if let Some(value) = value {
+                    brush.value = value;
+                }
+            }
+        }
+    }
+}
+
 #[derive(Default, Debug, Clone)]
 pub struct Brush {
     pub value: peniko::Brush,
     pub transform: kurbo::Affine,
+    pub units: BrushUnits,
+    /// Bevy [`Image`] asset that resolves into the [`value`][Self::value] once loaded.
+    pub image: Option<ImageBrush>,
+}
+
+/// Coordinate system of a [`Brush`], similar to SVG's `gradientUnits`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrushUnits {
+    /// Brush coordinates are in the local space of the shape.
+    #[default]
+    UserSpace,
+    /// Brush coordinates are relative to the bounding box of the shape,
+    /// where `(0, 0)` is the top left corner and `(1, 1)` is the bottom right corner.
+    ///
+    /// This keeps a gradient aligned with a shape as it resizes.
+    BoundingBox,
 }
 
 impl Brush {
@@ -19,14 +110,8 @@ impl Brush {
     }
 
     pub fn from_color(color: Color) -> Self {
-        let color = color.to_srgba();
         Self {
-            value: peniko::Brush::Solid(peniko::Color::new([
-                color.red,
-                color.green,
-                color.blue,
-                color.alpha,
-            ])),
+            value: peniko::Brush::Solid(to_peniko_color(color)),
             ..default()
         }
     }
@@ -38,8 +123,632 @@ impl Brush {
         }
     }
 
+    /// Creates a linear gradient from `start` to `end` with `(offset, color)` stops.
+    pub fn linear(
+        start: impl Into<DVec2>,
+        end: impl Into<DVec2>,
+        stops: impl IntoIterator<Item = (f32, Color)>,
+    ) -> Self {
+        Self::from_gradient(
+            peniko::Gradient::new_linear(to_point(start), to_point(end))
+                .with_stops(to_color_stops(stops).as_slice()),
+        )
+    }
+
+    /// Creates a radial gradient from `center` to `radius` with `(offset, color)` stops.
+    pub fn radial(
+        center: impl Into<DVec2>,
+        radius: f32,
+        stops: impl IntoIterator<Item = (f32, Color)>,
+    ) -> Self {
+        Self::from_gradient(
+            peniko::Gradient::new_radial(to_point(center), radius)
+                .with_stops(to_color_stops(stops).as_slice()),
+        )
+    }
+
+    /// Creates a radial gradient between two circles with `(offset, color)` stops.
+    pub fn two_point_radial(
+        start_center: impl Into<DVec2>,
+        start_radius: f32,
+        end_center: impl Into<DVec2>,
+        end_radius: f32,
+        stops: impl IntoIterator<Item = (f32, Color)>,
+    ) -> Self {
+        Self::from_gradient(
+            peniko::Gradient::new_two_point_radial(
+                to_point(start_center),
+                start_radius,
+                to_point(end_center),
+                end_radius,
+            )
+            .with_stops(to_color_stops(stops).as_slice()),
+        )
+    }
+
+    /// Creates a sweep gradient around `center` from `start_angle` to `end_angle` (in radians)
+    /// with `(offset, color)` stops.
+    pub fn sweep(
+        center: impl Into<DVec2>,
+        start_angle: f32,
+        end_angle: f32,
+        stops: impl IntoIterator<Item = (f32, Color)>,
+    ) -> Self {
+        Self::from_gradient(
+            peniko::Gradient::new_sweep(to_point(center), start_angle, end_angle)
+                .with_stops(to_color_stops(stops).as_slice()),
+        )
+    }
+
+    /// Creates an image brush from a Bevy [`Image`] asset.
+    ///
+    /// The brush is transparent until the image is loaded,
+    /// and it is updated whenever the image is modified.
+    pub fn from_image(handle: Handle<Image>) -> Self {
+        Self {
+            image: Some(ImageBrush::new(handle)),
+            ..default()
+        }
+    }
+
+    /// Creates an image brush that tiles a Bevy [`Image`] asset, e.g. for hatching and textures.
+    ///
+    /// The size of the tiles can be adjusted using [`Brush::with_transform`].
+    pub fn pattern(handle: Handle<Image>) -> Self {
+        Self::from_image(handle).with_extend(peniko::Extend::Repeat)
+    }
+
+    /// Sets the extend mode of a gradient or image brush.
+    pub fn with_extend(mut self, extend: peniko::Extend) -> Self {
+        if let Some(image) = &mut self.image {
+            image.x_extend = extend;
+            image.y_extend = extend;
+        }
+
+        match &mut self.value {
+            peniko::Brush::Gradient(gradient) => gradient.extend = extend,
+            peniko::Brush::Image(image) => {
+                image.x_extend = extend;
+                image.y_extend = extend;
+            }
+            peniko::Brush::Solid(_) => {}
+        }
+        self
+    }
+
+    /// Sets the sampling quality of an image brush.
+    pub fn with_quality(mut self, quality: peniko::ImageQuality) -> Self {
+        if let Some(image) = &mut self.image {
+            image.quality = quality;
+        }
+
+        if let peniko::Brush::Image(image) = &mut self.value {
+            image.quality = quality;
+        }
+        self
+    }
+
     pub fn with_transform(mut self, transform: kurbo::Affine) -> Self {
         self.transform = transform;
         self
     }
+
+    pub fn with_units(mut self, units: BrushUnits) -> Self {
+        self.units = units;
+        self
+    }
+
+    /// Returns the brush transform for drawing a `shape` based on the [`BrushUnits`].
+    pub fn resolve_transform(&self, shape: &impl kurbo::Shape) -> kurbo::Affine {
+        match self.units {
+            BrushUnits::UserSpace => self.transform,
+            BrushUnits::BoundingBox => {
+                let bounds = shape.bounding_box();
+                // Prevent a degenerate transform for shapes without a width or height.
+                let width = if bounds.width() > 0.0 {
+                    bounds.width()
+                } else {
+                    1.0
+                };
+                let height = if bounds.height() > 0.0 {
+                    bounds.height()
+                } else {
+                    1.0
+                };
+
+                kurbo::Affine::new([width, 0.0, 0.0, height, bounds.x0, bounds.y0]) * self.transform
+            }
+        }
+    }
+
+    /// Interpolates from `self` to `other` by `t`, mixing colors in the given color `space`.
+    ///
+    /// Gradients are resampled at the stop offsets of both brushes, and a solid color is treated
+    /// as a gradient with the same color at every stop. Properties that cannot be interpolated,
+    /// like image brushes, units or different gradient kinds, switch over at `t = 0.5`.
+    pub fn interpolate(&self, other: &Brush, t: f32, space: InterpolationSpace) -> Self {
+        if t <= 0.0 {
+            return self.clone();
+        }
+        if t >= 1.0 {
+            return other.clone();
+        }
+
+        let snapped = if t < 0.5 { self } else { other };
+        if self.image.is_some() || other.image.is_some() || self.units != other.units {
+            return snapped.clone();
+        }
+
+        let value = match (&self.value, &other.value) {
+            (peniko::Brush::Solid(a), peniko::Brush::Solid(b)) => {
+                peniko::Brush::Solid(mix_colors(*a, *b, t, space))
+            }
+            (peniko::Brush::Gradient(a), peniko::Brush::Gradient(b)) => {
+                peniko::Brush::Gradient(mix_gradients(a, b, t, space))
+            }
+            (peniko::Brush::Solid(a), peniko::Brush::Gradient(b)) => {
+                peniko::Brush::Gradient(mix_gradients(&solid_gradient(b, *a), b, t, space))
+            }
+            (peniko::Brush::Gradient(a), peniko::Brush::Solid(b)) => {
+                peniko::Brush::Gradient(mix_gradients(a, &solid_gradient(a, *b), t, space))
+            }
+            _ => snapped.value.clone(),
+        };
+
+        let (a, b) = (self.transform.as_coeffs(), other.transform.as_coeffs());
+        let transform =
+            kurbo::Affine::new(std::array::from_fn(|i| a[i] + (b[i] - a[i]) * t as f64));
+
+        Self {
+            value,
+            transform,
+            units: self.units,
+            image: None,
+        }
+    }
+}
+
+/// Color space used for interpolating between [`Brush`]es.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterpolationSpace {
+    /// Gamma encoded sRGB, matching how gradients are rendered.
+    #[default]
+    Srgb,
+    /// Linear sRGB, which is physically accurate for light but can appear uneven.
+    LinearRgb,
+    /// Oklab, a perceptually uniform color space.
+    Oklab,
+}
+
+/// A Bevy [`Image`] asset used as a [`Brush`].
+#[derive(Debug, Clone)]
+pub struct ImageBrush {
+    pub handle: Handle<Image>,
+    /// Extend mode in the horizontal direction.
+    pub x_extend: peniko::Extend,
+    /// Extend mode in the vertical direction.
+    pub y_extend: peniko::Extend,
+    /// Sampling quality of the image.
+    pub quality: peniko::ImageQuality,
+}
+
+impl ImageBrush {
+    pub fn new(handle: Handle<Image>) -> Self {
+        Self {
+            handle,
+            x_extend: peniko::Extend::Pad,
+            y_extend: peniko::Extend::Pad,
+            quality: peniko::ImageQuality::Medium,
+        }
+    }
+}
+
+/// Components holding [`Brush`]es, allowing their [`ImageBrush`]es to be resolved.
+pub trait BrushHolder {
+    fn brushes(&self) -> impl Iterator<Item = &Brush>;
+
+    fn brushes_mut(&mut self) -> impl Iterator<Item = &mut Brush>;
+}
+
+/// Cache of Bevy [`Image`]s converted into [`peniko::Image`]s for [`ImageBrush`]es.
+#[derive(Resource, Default)]
+pub struct ImageBrushCache {
+    images: HashMap<AssetId<Image>, peniko::Image>,
+    /// Images that were loaded or modified since the last frame.
+    updated: HashSet<AssetId<Image>>,
+}
+
+impl ImageBrushCache {
+    /// Returns the converted image of an [`ImageBrush`] if it is loaded.
+    fn resolve(&mut self, images: &Assets<Image>, brush: &ImageBrush) -> Option<peniko::Image> {
+        let id = brush.handle.id();
+        let image = match self.images.entry(id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(to_peniko_image(images.get(id)?)?),
+        };
+
+        Some(
+            image
+                .clone()
+                .with_x_extend(brush.x_extend)
+                .with_y_extend(brush.y_extend)
+                .with_quality(brush.quality),
+        )
+    }
+}
+
+/// Converts a Bevy [`Image`] into a [`peniko::Image`] with 8-bit RGBA channels.
+///
+/// RGBA8 data is copied as is, other formats are converted through a dynamic image.
+fn to_peniko_image(image: &Image) -> Option<peniko::Image> {
+    let (width, height) = (image.width(), image.height());
+    // Both the sRGB and linear RGBA8 formats share the same layout.
+    let data = if image.texture_descriptor.format.add_srgb_suffix() == BevyDefault::bevy_default() {
+        image.data.clone()
+    } else {
+        image
+            .clone()
+            .try_into_dynamic()
+            .ok()?
+            .into_rgba8()
+            .into_raw()
+    };
+
+    Some(peniko::Image::new(
+        peniko::Blob::from(data),
+        peniko::ImageFormat::Rgba8,
+        width,
+        height,
+    ))
+}
+
+/// Converts a Bevy [`Color`] into a [`peniko::Color`] in the sRGB color space.
+pub(crate) fn to_peniko_color(color: Color) -> peniko::Color {
+    let color = color.to_srgba();
+    peniko::Color::new([color.red, color.green, color.blue, color.alpha])
+}
+
+/// Mix two [`peniko::Color`]s by `t` in a color `space`.
+fn mix_colors(
+    a: peniko::Color,
+    b: peniko::Color,
+    t: f32,
+    space: InterpolationSpace,
+) -> peniko::Color {
+    let (a, b) = (
+        Srgba::from_f32_array(a.components),
+        Srgba::from_f32_array(b.components),
+    );
+
+    let color = match space {
+        InterpolationSpace::Srgb => a.mix(&b, t),
+        InterpolationSpace::LinearRgb => LinearRgba::from(a).mix(&b.into(), t).into(),
+        InterpolationSpace::Oklab => Oklaba::from(a).mix(&b.into(), t).into(),
+    };
+
+    peniko::Color::new(color.to_f32_array())
+}
+
+/// Mix two [`peniko::Gradient`]s by `t`, with colors mixed in a color `space`.
+fn mix_gradients(
+    a: &peniko::Gradient,
+    b: &peniko::Gradient,
+    t: f32,
+    space: InterpolationSpace,
+) -> peniko::Gradient {
+    use peniko::GradientKind;
+
+    let lerp = |a: f32, b: f32| a + (b - a) * t;
+    let lerp_point = |a: kurbo::Point, b: kurbo::Point| a.lerp(b, t as f64);
+
+    let snapped = if t < 0.5 { a } else { b };
+    let kind = match (a.kind, b.kind) {
+        (
+            GradientKind::Linear { start: s0, end: e0 },
+            GradientKind::Linear { start: s1, end: e1 },
+        ) => GradientKind::Linear {
+            start: lerp_point(s0, s1),
+            end: lerp_point(e0, e1),
+        },
+        (
+            GradientKind::Radial {
+                start_center: sc0,
+                start_radius: sr0,
+                end_center: ec0,
+                end_radius: er0,
+            },
+            GradientKind::Radial {
+                start_center: sc1,
+                start_radius: sr1,
+                end_center: ec1,
+                end_radius: er1,
+            },
+        ) => GradientKind::Radial {
+            start_center: lerp_point(sc0, sc1),
+            start_radius: lerp(sr0, sr1),
+            end_center: lerp_point(ec0, ec1),
+            end_radius: lerp(er0, er1),
+        },
+        (
+            GradientKind::Sweep {
+                center: c0,
+                start_angle: sa0,
+                end_angle: ea0,
+            },
+            GradientKind::Sweep {
+                center: c1,
+                start_angle: sa1,
+                end_angle: ea1,
+            },
+        ) => GradientKind::Sweep {
+            center: lerp_point(c0, c1),
+            start_angle: lerp(sa0, sa1),
+            end_angle: lerp(ea0, ea1),
+        },
+        _ => snapped.kind,
+    };
+
+    // Resample both gradients at the offsets of all stops.
+    let mut offsets = a
+        .stops
+        .iter()
+        .chain(b.stops.iter())
+        .map(|stop| stop.offset)
+        .collect::<Vec<_>>();
+    offsets.sort_by(f32::total_cmp);
+    offsets.dedup();
+
+    let stops = offsets
+        .into_iter()
+        .map(|offset| {
+            let color = mix_colors(sample_stops(a, offset), sample_stops(b, offset), t, space);
+            peniko::ColorStop::from((offset, color))
+        })
+        .collect::<Vec<_>>();
+
+    peniko::Gradient {
+        kind,
+        ..snapped.clone()
+    }
+    .with_stops(stops.as_slice())
+}
+
+/// Sample the color of a `gradient` at a stop `offset`.
+fn sample_stops(gradient: &peniko::Gradient, offset: f32) -> peniko::Color {
+    let color = |stop: &peniko::ColorStop| stop.color.to_alpha_color::<peniko::color::Srgb>();
+
+    let stops = &gradient.stops;
+    let index = stops.partition_point(|stop| stop.offset <= offset);
+
+    match (index.checked_sub(1).map(|i| &stops[i]), stops.get(index)) {
+        (Some(s0), Some(s1)) => {
+            let t = (offset - s0.offset) / (s1.offset - s0.offset);
+            // Gradients are rendered with sRGB interpolation by default.
+            mix_colors(color(s0), color(s1), t, InterpolationSpace::Srgb)
+        }
+        (Some(stop), None) | (None, Some(stop)) => color(stop),
+        (None, None) => peniko::Color::TRANSPARENT,
+    }
+}
+
+/// Creates a gradient with the geometry and stop offsets of a `gradient`,
+/// but with the same `color` at every stop.
+fn solid_gradient(gradient: &peniko::Gradient, color: peniko::Color) -> peniko::Gradient {
+    let stops = if gradient.stops.is_empty() {
+        vec![peniko::ColorStop::from((0.0, color))]
+    } else {
+        gradient
+            .stops
+            .iter()
+            .map(|stop| peniko::ColorStop::from((stop.offset, color)))
+            .collect()
+    };
+
+    gradient.clone().with_stops(stops.as_slice())
+}
+
+fn to_color_stops(stops: impl IntoIterator<Item = (f32, Color)>) -> Vec<peniko::ColorStop> {
+    stops
+        .into_iter()
+        .map(|(offset, color)| peniko::ColorStop::from((offset, to_peniko_color(color))))
+        .collect()
+}
+
+fn to_point(point: impl Into<DVec2>) -> kurbo::Point {
+    let point = point.into();
+    kurbo::Point::new(point.x, point.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::prelude::*;
+
+    use bevy_math::Vec2;
+
+    use super::*;
+    use crate::fill::Fill;
+
+    #[test]
+    fn changed_image_resets_to_placeholder() {
+        let mut app = App::new();
+        app.init_resource::<Assets<Image>>()
+            .init_resource::<ImageBrushCache>()
+            .add_event::<AssetEvent<Image>>()
+            .add_systems(
+                Update,
+                (update_image_brush_cache, sync_image_brushes::<Fill>).chain(),
+            );
+
+        let loaded = app
+            .world_mut()
+            .resource_mut::<Assets<Image>>()
+            .add(Image::default());
+        let entity = app
+            .world_mut()
+            .spawn(Fill::new().with_brush(Brush::from_image(loaded)))
+            .id();
+        app.update();
+        assert!(matches!(
+            app.world().get::<Fill>(entity).unwrap().brush.value,
+            peniko::Brush::Image(_)
+        ));
+
+        let pending = app
+            .world_mut()
+            .resource_mut::<Assets<Image>>()
+            .reserve_handle();
+        app.world_mut()
+            .get_mut::<Fill>(entity)
+            .unwrap()
+            .brush
+            .image
+            .as_mut()
+            .unwrap()
+            .handle = pending;
+        app.update();
+        assert_eq!(
+            app.world().get::<Fill>(entity).unwrap().brush.value,
+            peniko::Brush::default()
+        );
+    }
+
+    fn assert_color(color: peniko::Color, expected: [f32; 4]) {
+        let close = color
+            .components
+            .iter()
+            .zip(expected)
+            .all(|(a, b)| (a - b).abs() < 1e-4);
+        assert!(close, "{:?} != {expected:?}", color.components);
+    }
+
+    fn stop_colors(brush: &Brush) -> Vec<(f32, peniko::Color)> {
+        let peniko::Brush::Gradient(gradient) = &brush.value else {
+            panic!("{:?} is not a gradient", brush.value);
+        };
+
+        gradient
+            .stops
+            .iter()
+            .map(|stop| {
+                let color = stop.color.to_alpha_color::<peniko::color::Srgb>();
+                (stop.offset, color)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn interpolate_endpoints() {
+        let a = Brush::from_color(Color::srgb(1.0, 0.0, 0.0))
+            .with_transform(kurbo::Affine::translate((1.0, 2.0)));
+        let b = Brush::linear(
+            Vec2::ZERO,
+            Vec2::X,
+            [(0.0, Color::BLACK), (1.0, Color::WHITE)],
+        );
+
+        for (t, expected) in [(-1.0, &a), (0.0, &a), (1.0, &b), (2.0, &b)] {
+            let brush = a.interpolate(&b, t, InterpolationSpace::Srgb);
+            assert_eq!(brush.value, expected.value);
+            assert_eq!(brush.transform, expected.transform);
+        }
+    }
+
+    #[test]
+    fn interpolate_solids() {
+        let a = Brush::from_color(Color::srgb(1.0, 0.0, 0.0))
+            .with_transform(kurbo::Affine::translate((2.0, 0.0)));
+        let b = Brush::from_color(Color::srgb(0.0, 0.0, 1.0));
+
+        let brush = a.interpolate(&b, 0.25, InterpolationSpace::Srgb);
+        let peniko::Brush::Solid(color) = brush.value else {
+            panic!("{:?} is not solid", brush.value);
+        };
+        assert_color(color, [0.75, 0.0, 0.25, 1.0]);
+        assert_eq!(brush.transform, kurbo::Affine::translate((1.5, 0.0)));
+    }
+
+    #[test]
+    fn interpolation_spaces() {
+        let a = Brush::from_color(Color::BLACK);
+        let b = Brush::from_color(Color::WHITE);
+
+        // The sRGB encoded midpoints of linear light and of perceptual lightness.
+        for (space, gray) in [
+            (InterpolationSpace::Srgb, 0.5),
+            (InterpolationSpace::LinearRgb, 0.735_357),
+            (InterpolationSpace::Oklab, 0.388_572),
+        ] {
+            let peniko::Brush::Solid(color) = a.interpolate(&b, 0.5, space).value else {
+                panic!("{space:?} did not interpolate into a solid color");
+            };
+            assert_color(color, [gray, gray, gray, 1.0]);
+        }
+    }
+
+    #[test]
+    fn interpolate_mismatched_stops() {
+        let red = Color::srgb(1.0, 0.0, 0.0);
+        let green = Color::srgb(0.0, 1.0, 0.0);
+        let blue = Color::srgb(0.0, 0.0, 1.0);
+        let a = Brush::linear(Vec2::ZERO, Vec2::X, [(0.0, red), (1.0, blue)]);
+        let b = Brush::linear(
+            Vec2::ZERO,
+            Vec2::new(3.0, 0.0),
+            [(0.0, red), (0.5, green), (1.0, blue)],
+        );
+
+        let brush = a.interpolate(&b, 0.5, InterpolationSpace::Srgb);
+        let stops = stop_colors(&brush);
+        assert_eq!(
+            stops.iter().map(|(offset, _)| *offset).collect::<Vec<_>>(),
+            [0.0, 0.5, 1.0]
+        );
+        assert_color(stops[0].1, [1.0, 0.0, 0.0, 1.0]);
+        // Halfway between the sampled purple of `a` and the green stop of `b`.
+        assert_color(stops[1].1, [0.25, 0.5, 0.25, 1.0]);
+        assert_color(stops[2].1, [0.0, 0.0, 1.0, 1.0]);
+
+        let peniko::Brush::Gradient(gradient) = &brush.value else {
+            unreachable!();
+        };
+        let peniko::GradientKind::Linear { end, .. } = gradient.kind else {
+            panic!("{:?} is not linear", gradient.kind);
+        };
+        assert_eq!(end, kurbo::Point::new(2.0, 0.0));
+    }
+
+    #[test]
+    fn interpolate_gradient_and_solid() {
+        let solid = Brush::from_color(Color::WHITE);
+        let gradient = Brush::linear(
+            Vec2::ZERO,
+            Vec2::X,
+            [
+                (0.0, Color::srgb(1.0, 0.0, 0.0)),
+                (1.0, Color::srgb(0.0, 0.0, 1.0)),
+            ],
+        );
+
+        // Halfway is the same in both directions.
+        for brush in [
+            solid.interpolate(&gradient, 0.5, InterpolationSpace::Srgb),
+            gradient.interpolate(&solid, 0.5, InterpolationSpace::Srgb),
+        ] {
+            let stops = stop_colors(&brush);
+            assert_eq!(stops.len(), 2);
+            assert_color(stops[0].1, [1.0, 0.5, 0.5, 1.0]);
+            assert_color(stops[1].1, [0.5, 0.5, 1.0, 1.0]);
+        }
+
+        let stops = stop_colors(&solid.interpolate(&gradient, 0.25, InterpolationSpace::Srgb));
+        assert_color(stops[0].1, [1.0, 0.75, 0.75, 1.0]);
+    }
+
+    #[test]
+    fn rgba8_image_data_is_copied() {
+        let image = Image::default();
+        let converted = to_peniko_image(&image).unwrap();
+        assert_eq!(converted.data.data(), image.data.as_slice());
+        assert_eq!((converted.width, converted.height), (1, 1));
+    }
 }
@@ -27,6 +27,14 @@ impl Vector for VelloCircle {
         kurbo::Circle::new(kurbo::Point::default(), self.radius)
     }
 
+    fn rounded_rect(&self) -> Option<kurbo::RoundedRect> {
+        Some(kurbo::RoundedRect::from_origin_size(
+            (-self.radius, -self.radius),
+            (self.radius * 2.0, self.radius * 2.0),
+            self.radius,
+        ))
+    }
+
     fn border_translation(&self, time: f64) -> DVec2 {
         let theta = time * std::f64::consts::TAU;
         DVec2::new(f64::sin(theta), f64::cos(theta)) * self.radius
@@ -0,0 +1,84 @@
+//! Clipping [`Vector`] shapes with another [`Vector`] shape.
+
+use bevy_ecs::prelude::*;
+use bevy_vello::vello::{self, kurbo, kurbo::Shape, peniko};
+
+use crate::{vector::TOLERANCE, Vector};
+
+/// Prepare [`ClipPath`]s from [`Clip`] vectors.
+pub(super) fn prepare_clips<V: Vector + Component>(
+    q_clips: Query<(Entity, &Clip<V>), Changed<Clip<V>>>,
+    mut q_clip_paths: Query<&mut ClipPath>,
+    mut removed_clips: RemovedComponents<Clip<V>>,
+) {
+    for entity in removed_clips.read() {
+        if let Ok(mut clip_path) = q_clip_paths.get_mut(entity) {
+            clip_path.0 = None;
+        }
+    }
+
+    for (entity, clip) in q_clips.iter() {
+        if let Ok(mut clip_path) = q_clip_paths.get_mut(entity) {
+            let mut path = clip.vector.shape().to_path(TOLERANCE);
+            path.apply_affine(clip.transform);
+            clip_path.0 = Some(path);
+        }
+    }
+}
+
+/// Clips the fill, stroke and head of a [`Vector`] shape to the shape of another [`Vector`].
+///
+/// The clip shape is defined in the local space of the clipped entity
+/// and can be animated like any other vector.
+#[derive(Component, Default, Debug, Clone, Copy)]
+#[require(ClipPath)]
+pub struct Clip<V: Vector + Send + Sync + 'static> {
+    /// Vector defining the shape of the clip.
+    pub vector: V,
+    /// Transform of the clip shape.
+    pub transform: kurbo::Affine,
+}
+
+impl<V: Vector + Send + Sync + 'static> Clip<V> {
+    pub fn new(vector: V) -> Self {
+        Self {
+            vector,
+            transform: kurbo::Affine::IDENTITY,
+        }
+    }
+
+    pub fn with_transform(mut self, transform: kurbo::Affine) -> Self {
+        self.transform = transform;
+        self
+    }
+}
+
+/// A read-only computed [`Clip`] path.
+///
+/// The path is computed in the [`PrepareClip`][PrepareClip] system set.
+///
+/// [PrepareClip]: crate::PrepareClip
+#[derive(Component, Default, Debug, Clone)]
+pub struct ClipPath(Option<kurbo::BezPath>);
+
+impl ClipPath {
+    pub fn path(&self) -> Option<&kurbo::BezPath> {
+        self.0.as_ref()
+    }
+}
+
+/// Draw into `scene` with the [`ClipPath`] applied (if any).
+pub(crate) fn draw_clipped(
+    scene: &mut vello::Scene,
+    clip_path: Option<&ClipPath>,
+    draw: impl FnOnce(&mut vello::Scene),
+) {
+    match clip_path.and_then(ClipPath::path) {
+        Some(path) => {
+            scene.push_layer(peniko::Mix::Clip, 1.0, kurbo::Affine::IDENTITY, path);
+            draw(scene);
+            scene.pop_layer();
+        }
+        None => draw(scene),
+    }
+}
@@ -5,7 +5,7 @@ use bevy_ecs::prelude::*;
 use bevy_utils::prelude::*;
 use bevy_vello::prelude::*;
 
-use crate::brush::Brush;
+use crate::brush::{Brush, BrushHolder};
 
 /// Fill of a [`Vector`][Vector].
 ///
@@ -44,3 +44,42 @@ impl Default for Fill {
         }
     }
 }
+
+impl BrushHolder for Fill {
+    fn brushes(&self) -> impl Iterator<Item = &Brush> {
+        std::iter::once(&self.brush)
+    }
+
+    fn brushes_mut(&mut self) -> impl Iterator<Item = &mut Brush> {
+        std::iter::once(&mut self.brush)
+    }
+}
+
+/// Multiple [`Fill`]s of a [`Vector`][Vector], drawn in order on top of each other.
+///
+/// If a [`Fill`] is also present, it is drawn first.
+///
+/// [Vector]: crate::Vector
+#[derive(Component, Default, Debug, Clone)]
+pub struct Fills(pub Vec<Fill>);
+
+impl Fills {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_fill(mut self, fill: Fill) -> Self {
+        self.0.push(fill);
+        self
+    }
+}
+
+impl BrushHolder for Fills {
+    fn brushes(&self) -> impl Iterator<Item = &Brush> {
+        self.0.iter().map(|fill| &fill.brush)
+    }
+
+    fn brushes_mut(&mut self) -> impl Iterator<Item = &mut Brush> {
+        self.0.iter_mut().map(|fill| &mut fill.brush)
+    }
+}
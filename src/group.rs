@@ -0,0 +1,213 @@
+//! Compositing descendant shapes into a single [`VelloScene`].
+
+use bevy_ecs::{entity::EntityHashSet, prelude::*};
+use bevy_hierarchy::prelude::*;
+use bevy_transform::prelude::*;
+use bevy_vello::prelude::*;
+
+use crate::{
+    blend::{draw_blended, BlendMode, Opacity},
+    clip::{draw_clipped, ClipPath},
+    vector::flip_y,
+    SceneHolder,
+};
+
+/// Queue [`VelloGroup`]s with changes to themselves or their members to be recomposited.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub(super) fn queue_groups(
+    mut queue: ResMut<GroupQueue>,
+    q_changed_groups: Query<
+        Entity,
+        (
+            With<VelloGroup>,
+            With<VelloScene>,
+            Or<(
+                Changed<VelloGroup>,
+                Changed<SceneHolder<CompositeScene>>,
+                Changed<Children>,
+                Changed<Opacity>,
+                Changed<BlendMode>,
+                Changed<ClipPath>,
+            )>,
+        ),
+    >,
+    q_changed_members: Query<
+        Entity,
+        (
+            Without<VelloScene>,
+            Or<(
+                Changed<SceneHolder<CompositeScene>>,
+                Changed<Transform>,
+                Changed<Children>,
+                Changed<Parent>,
+            )>,
+        ),
+    >,
+    q_added_scenes: Query<Entity, Added<VelloScene>>,
+    q_ancestors: Query<(Option<&Parent>, Has<VelloScene>, Has<VelloGroup>)>,
+    mut removed_member_scenes: RemovedComponents<SceneHolder<CompositeScene>>,
+    mut removed_transforms: RemovedComponents<Transform>,
+    mut removed_children: RemovedComponents<Children>,
+    mut removed_scenes: RemovedComponents<VelloScene>,
+    mut removed_opacities: RemovedComponents<Opacity>,
+    mut removed_blend_modes: RemovedComponents<BlendMode>,
+    mut removed_clip_paths: RemovedComponents<ClipPath>,
+) {
+    let groups = &mut queue.0;
+    groups.extend(q_changed_groups.iter());
+
+    // Find the group that an entity is composited into, if any.
+    let mut find_group = |mut entity: Entity| {
+        while let Ok((parent, has_scene, is_group)) = q_ancestors.get(entity) {
+            // Entities with their own `VelloScene` are rendered on their own.
+            if has_scene {
+                if is_group {
+                    groups.insert(entity);
+                }
+                return;
+            }
+
+            let Some(parent) = parent else {
+                return;
+            };
+            entity = parent.get();
+        }
+    };
+
+    for entity in q_changed_members
+        .iter()
+        .chain(removed_member_scenes.read())
+        .chain(removed_transforms.read())
+        .chain(removed_children.read())
+        .chain(removed_opacities.read())
+        .chain(removed_blend_modes.read())
+        .chain(removed_clip_paths.read())
+    {
+        find_group(entity);
+    }
+
+    // Entities that gain or lose their own `VelloScene` join or leave the group of their parent.
+    for entity in q_added_scenes.iter().chain(removed_scenes.read()) {
+        if let Ok((Some(parent), ..)) = q_ancestors.get(entity) {
+            find_group(parent.get());
+        }
+    }
+}
+
+/// Composite the [`CompositeScene`]s of all members of queued [`VelloGroup`]s
+/// into the group's [`VelloScene`].
+#[allow(clippy::type_complexity)]
+pub(super) fn composite_groups(
+    mut commands: Commands,
+    mut queue: ResMut<GroupQueue>,
+    q_groups: Query<
+        (
+            Option<&SceneHolder<CompositeScene>>,
+            Option<&Children>,
+            Option<&Opacity>,
+            Option<&BlendMode>,
+            Option<&ClipPath>,
+        ),
+        (With<VelloGroup>, With<VelloScene>),
+    >,
+    q_members: Query<
+        (
+            Option<&SceneHolder<CompositeScene>>,
+            Option<&Transform>,
+            Option<&Children>,
+        ),
+        Without<VelloScene>,
+    >,
+) {
+    for entity in queue.0.drain() {
+        let Ok((group_scene, children, opacity, blend_mode, clip_path)) = q_groups.get(entity)
+        else {
+            continue;
+        };
+
+        // Collect member scenes in pre-order, following the order of children.
+        let mut member_scenes = Vec::new();
+        let mut stack = children
+            .iter()
+            .flat_map(|children| children.iter().rev())
+            .map(|child| (*child, kurbo::Affine::IDENTITY))
+            .collect::<Vec<_>>();
+
+        while let Some((member, parent_affine)) = stack.pop() {
+            // Members with their own `VelloScene` are rendered on their own.
+            let Ok((member_scene, transform, member_children)) = q_members.get(member) else {
+                continue;
+            };
+
+            let affine =
+                parent_affine * transform.map_or(kurbo::Affine::IDENTITY, transform_to_affine);
+
+            if let Some(member_scene) = member_scene {
+                member_scenes.push((affine, member_scene));
+            }
+
+            if let Some(member_children) = member_children {
+                stack.extend(member_children.iter().rev().map(|child| (*child, affine)));
+            }
+        }
+
+        let mut scene = vello::Scene::new();
+        draw_blended(&mut scene, opacity, blend_mode, |scene| {
+            // The group's own layers are already clipped when drawn.
+            if let Some(group_scene) = group_scene {
+                scene.append(group_scene.scene(), None);
+            }
+
+            draw_clipped(scene, clip_path, |scene| {
+                for (affine, member_scene) in member_scenes {
+                    scene.append(member_scene.scene(), Some(affine));
+                }
+            });
+        });
+
+        commands.entity(entity).insert(VelloScene::from(scene));
+    }
+}
+
+/// Converts a Bevy [`Transform`] into a Vello [`kurbo::Affine`].
+pub(crate) fn transform_to_affine(transform: &Transform) -> kurbo::Affine {
+    let affine = transform.compute_affine();
+    let (x_axis, y_axis, translation) = (
+        affine.matrix3.x_axis,
+        affine.matrix3.y_axis,
+        affine.translation,
+    );
+
+    flip_y(kurbo::Affine::new([
+        x_axis.x as f64,
+        x_axis.y as f64,
+        y_axis.x as f64,
+        y_axis.y as f64,
+        translation.x as f64,
+        translation.y as f64,
+    ]))
+}
+
+/// [`VelloGroup`]s to be recomposited.
+#[derive(Resource, Default)]
+pub(crate) struct GroupQueue(EntityHashSet);
+
+/// Marker struct of a composited scene for [`SceneHolder`].
+///
+/// Entities without their own [`VelloScene`] (and [`VelloGroup`]s) store the result of
+/// compositing their layers in this scene instead.
+pub struct CompositeScene;
+
+/// Composites the shapes of all descendant entities into this entity's [`VelloScene`].
+///
+/// Descendants are drawn after the group's own shape, in the order of their [`Children`],
+/// using their [`Transform`] relative to the group (or no transformation if they have none).
+/// Descendants with their own [`VelloScene`] (including nested groups) are rendered on their own
+/// and skipped together with their descendants.
+///
+/// [`Opacity`], [`BlendMode`] and [`Clip`][Clip] on the group apply to the whole group.
+/// The group's own shape is clipped only once, like any other clipped shape.
+///
+/// [Clip]: crate::clip::Clip
+#[derive(Component, Default, Debug, Clone, Copy)]
+pub struct VelloGroup;
@@ -0,0 +1,403 @@
+//! Procedural hatch and stipple fills.
+
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+use bevy_color::Color;
+use bevy_ecs::prelude::*;
+use bevy_utils::prelude::*;
+use bevy_vello::prelude::*;
+use kurbo::Shape;
+
+use crate::{
+    blend::LAYER_BOUNDS,
+    brush::{Brush, BrushHolder},
+    hit,
+    vector::TOLERANCE,
+};
+
+/// Maximum number of lines in each direction of a line pattern.
+const MAX_LINES: f64 = 4096.0;
+/// Maximum number of dots in a dot pattern.
+const MAX_DOTS: f64 = 65536.0;
+
+/// A procedural pattern fill of a [`Vector`][Vector].
+///
+/// The pattern is generated as vector geometry clipped inside the shape, so it stays crisp
+/// at any zoom level. It is drawn on top of the [`Fill`][Fill]s of the shape, and clipped with
+/// the fill rule of the first of them ([`peniko::Fill::NonZero`] if there are none).
+///
+/// The spacing is increased as needed to limit the pattern to 4096 lines in each direction,
+/// or 65536 dots, so that large shapes stay cheap to draw.
+///
+/// [Vector]: crate::Vector
+/// [Fill]: crate::fill::Fill
+#[derive(Component, Debug, Clone)]
+pub struct HatchFill {
+    pub pattern: HatchPattern,
+    /// Distance between adjacent lines or dots.
+    pub spacing: f64,
+    /// Rotation of the pattern in radians.
+    pub angle: f64,
+    /// Width of the lines, or diameter of the dots.
+    pub line_width: f64,
+    pub brush: Brush,
+}
+
+impl HatchFill {
+    pub fn new(pattern: HatchPattern) -> Self {
+        Self {
+            pattern,
+            ..default()
+        }
+    }
+
+    /// Creates parallel diagonal lines.
+    pub fn diagonal() -> Self {
+        Self::new(HatchPattern::Lines).with_angle(FRAC_PI_4)
+    }
+
+    /// Creates two sets of perpendicular diagonal lines.
+    pub fn cross_hatch() -> Self {
+        Self::new(HatchPattern::CrossHatch).with_angle(FRAC_PI_4)
+    }
+
+    /// Creates dots on a regular grid.
+    pub fn dot_grid() -> Self {
+        Self::new(HatchPattern::DotGrid)
+    }
+
+    /// Creates dots randomly scattered from a `seed`, with one dot per grid cell.
+    pub fn stipple(seed: u32) -> Self {
+        Self::new(HatchPattern::Stipple { seed })
+    }
+
+    pub fn with_spacing(mut self, spacing: f64) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    pub fn with_angle(mut self, angle: f64) -> Self {
+        self.angle = angle;
+        self
+    }
+
+    pub fn with_line_width(mut self, line_width: f64) -> Self {
+        self.line_width = line_width;
+        self
+    }
+
+    pub fn with_brush(mut self, brush: Brush) -> Self {
+        self.brush = brush;
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.brush = Brush::from_color(color);
+        self
+    }
+
+    /// Draw the pattern clipped inside a `shape` filled with the `fill_rule`.
+    pub(crate) fn draw(
+        &self,
+        scene: &mut vello::Scene,
+        transform: kurbo::Affine,
+        shape: &impl kurbo::Shape,
+        fill_rule: peniko::Fill,
+    ) {
+        let path = self.pattern_path(shape);
+        if path.elements().is_empty() {
+            return;
+        }
+
+        let brush_transform = Some(self.brush.resolve_transform(shape));
+
+        match fill_rule {
+            peniko::Fill::NonZero => scene.push_layer(peniko::Mix::Clip, 1.0, transform, shape),
+            // Clip layers are always non-zero, the pattern is masked by the filled shape instead.
+            peniko::Fill::EvenOdd => scene.push_layer(
+                peniko::Mix::Normal,
+                1.0,
+                kurbo::Affine::IDENTITY,
+                &LAYER_BOUNDS,
+            ),
+        }
+
+        match self.pattern {
+            HatchPattern::Lines | HatchPattern::CrossHatch => scene.stroke(
+                &kurbo::Stroke::new(self.line_width),
+                transform,
+                &self.brush.value,
+                brush_transform,
+                &path,
+            ),
+            HatchPattern::DotGrid | HatchPattern::Stipple { .. } => scene.fill(
+                peniko::Fill::NonZero,
+                transform,
+                &self.brush.value,
+                brush_transform,
+                &path,
+            ),
+        }
+
+        if fill_rule == peniko::Fill::EvenOdd {
+            scene.push_layer(
+                peniko::BlendMode::new(peniko::Mix::Normal, peniko::Compose::DestIn),
+                1.0,
+                kurbo::Affine::IDENTITY,
+                &LAYER_BOUNDS,
+            );
+            scene.fill(fill_rule, transform, peniko::Color::BLACK, None, shape);
+            scene.pop_layer();
+        }
+
+        scene.pop_layer();
+    }
+
+    /// Returns true if the `point` is within the area covered by the pattern,
+    /// which is the inside of a `shape` filled with the `fill_rule`.
+    pub fn contains(
+        &self,
+        shape: &impl kurbo::Shape,
+        point: kurbo::Point,
+        fill_rule: peniko::Fill,
+    ) -> bool {
+        hit::fill_contains(shape, point, fill_rule)
+    }
+
+    /// Generates the pattern covering the bounding box of a `shape`, before clipping.
+    ///
+    /// Lines are meant to be stroked with the [`line_width`][Self::line_width],
+    /// while dots are meant to be filled. The pattern is empty if the shape has no finite bounds.
+    pub fn pattern_path(&self, shape: &impl kurbo::Shape) -> kurbo::BezPath {
+        let mut path = kurbo::BezPath::new();
+        if !self.spacing.is_finite() || self.spacing <= 0.0 {
+            return path;
+        }
+
+        let bounds = shape.bounding_box();
+        if !bounds.is_finite() {
+            return path;
+        }
+
+        match self.pattern {
+            HatchPattern::Lines => self.push_lines(&mut path, bounds, self.angle),
+            HatchPattern::CrossHatch => {
+                self.push_lines(&mut path, bounds, self.angle);
+                self.push_lines(&mut path, bounds, self.angle + FRAC_PI_2);
+            }
+            HatchPattern::DotGrid => self.push_dots(&mut path, bounds, |_, _| (0.0, 0.0)),
+            HatchPattern::Stipple { seed } => self.push_dots(&mut path, bounds, |x, y| {
+                let seed = u64::from(seed) << 1;
+                (cell_random(x, y, seed), cell_random(x, y, seed | 1))
+            }),
+        }
+
+        path
+    }
+
+    /// Push lines at an `angle` covering the `bounds` into `path`.
+    fn push_lines(&self, path: &mut kurbo::BezPath, bounds: kurbo::Rect, angle: f64) {
+        let rotation = kurbo::Affine::rotate(angle);
+        let bounds = rotation.inverse().transform_rect_bbox(bounds);
+        let spacing = limit_spacing(self.spacing, MAX_LINES, |spacing| {
+            cell_count(bounds.y0, bounds.y1, spacing)
+        });
+
+        for y in cell_range(bounds.y0, bounds.y1, spacing) {
+            let y = y as f64 * spacing;
+            path.move_to(rotation * kurbo::Point::new(bounds.x0, y));
+            path.line_to(rotation * kurbo::Point::new(bounds.x1, y));
+        }
+    }
+
+    /// Push a dot for every grid cell covering the `bounds` into `path`.
+    ///
+    /// Each dot is offset by a fraction of the spacing returned by `jitter` for its cell.
+    fn push_dots(
+        &self,
+        path: &mut kurbo::BezPath,
+        bounds: kurbo::Rect,
+        jitter: impl Fn(i64, i64) -> (f64, f64),
+    ) {
+        let rotation = kurbo::Affine::rotate(self.angle);
+        let bounds = rotation.inverse().transform_rect_bbox(bounds);
+        let radius = self.line_width * 0.5;
+        let spacing = limit_spacing(self.spacing, MAX_DOTS, |spacing| {
+            cell_count(bounds.x0, bounds.x1, spacing) * cell_count(bounds.y0, bounds.y1, spacing)
+        });
+
+        for y in cell_range(bounds.y0, bounds.y1, spacing) {
+            for x in cell_range(bounds.x0, bounds.x1, spacing) {
+                let (dx, dy) = jitter(x, y);
+                let center =
+                    kurbo::Point::new((x as f64 + dx) * spacing, (y as f64 + dy) * spacing);
+
+                path.extend(kurbo::Circle::new(rotation * center, radius).path_elements(TOLERANCE));
+            }
+        }
+    }
+}
+
+impl Default for HatchFill {
+    fn default() -> Self {
+        Self {
+            pattern: default(),
+            spacing: 8.0,
+            angle: 0.0,
+            line_width: 1.0,
+            brush: default(),
+        }
+    }
+}
+
+impl BrushHolder for HatchFill {
+    fn brushes(&self) -> impl Iterator<Item = &Brush> {
+        std::iter::once(&self.brush)
+    }
+
+    fn brushes_mut(&mut self) -> impl Iterator<Item = &mut Brush> {
+        std::iter::once(&mut self.brush)
+    }
+}
+
+/// Pattern of a [`HatchFill`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HatchPattern {
+    /// Parallel lines.
+    #[default]
+    Lines,
+    /// Two sets of perpendicular lines.
+    CrossHatch,
+    /// Dots on a regular grid.
+    DotGrid,
+    /// Dots randomly scattered within each grid cell.
+    Stipple { seed: u32 },
+}
+
+/// Indices of the grid cells covering `min..=max` with a `spacing`.
+///
+/// Cells are aligned to the origin so that the pattern stays in place when the shape changes.
+fn cell_range(min: f64, max: f64, spacing: f64) -> std::ops::RangeInclusive<i64> {
+    (min / spacing).floor() as i64..=(max / spacing).ceil() as i64
+}
+
+/// Number of grid cells in the [`cell_range`] covering `min..=max` with a `spacing`.
+fn cell_count(min: f64, max: f64, spacing: f64) -> f64 {
+    (max / spacing).ceil() - (min / spacing).floor() + 1.0
+}
+
+/// Increases the `spacing` until the number of cells returned by `count` is at most `max_count`.
+fn limit_spacing(mut spacing: f64, max_count: f64, count: impl Fn(f64) -> f64) -> f64 {
+    loop {
+        let ratio = count(spacing) / max_count;
+        if ratio <= 1.0 {
+            return spacing;
+        }
+
+        // The count of a grid shrinks at least with the square root of the spacing.
+        spacing *= ratio.sqrt().max(1.0 + 1e-3);
+    }
+}
+
+/// Pseudo-random value in `[0.0, 1.0)` of a grid cell, stable across frames.
+fn cell_random(x: i64, y: i64, seed: u64) -> f64 {
+    let mut hash = (x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        ^ (y as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F)
+        ^ seed.wrapping_mul(0x1656_67B1_9E37_79F9);
+
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xFF51_AFD7_ED55_8CCD);
+    hash ^= hash >> 33;
+
+    (hash >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Number of subpaths, which is one per line or dot.
+    fn count(path: &kurbo::BezPath) -> usize {
+        path.elements()
+            .iter()
+            .filter(|el| matches!(el, kurbo::PathEl::MoveTo(_)))
+            .count()
+    }
+
+    #[test]
+    fn line_count() {
+        let rect = kurbo::Rect::new(0.0, 0.0, 100.0, 100.0);
+
+        let hatch = HatchFill::diagonal().with_angle(0.0).with_spacing(10.0);
+        assert_eq!(count(&hatch.pattern_path(&rect)), 11);
+
+        // Rounding of the rotated bounds may add a line at the edge.
+        let hatch = HatchFill::cross_hatch().with_angle(0.0).with_spacing(10.0);
+        let lines = count(&hatch.pattern_path(&rect));
+        assert!((22..=24).contains(&lines), "{lines}");
+    }
+
+    #[test]
+    fn line_count_is_limited() {
+        let rect = kurbo::Rect::new(0.0, 0.0, 1.0, 1e6);
+
+        let hatch = HatchFill::diagonal().with_angle(0.0).with_spacing(0.01);
+        let lines = count(&hatch.pattern_path(&rect));
+        assert!(lines <= MAX_LINES as usize, "{lines}");
+        assert!(lines > MAX_LINES as usize / 2, "{lines}");
+
+        let hatch = HatchFill::cross_hatch().with_spacing(0.01);
+        let lines = count(&hatch.pattern_path(&rect));
+        assert!(lines <= 2 * MAX_LINES as usize, "{lines}");
+    }
+
+    #[test]
+    fn dot_count() {
+        let rect = kurbo::Rect::new(0.0, 0.0, 100.0, 50.0);
+
+        let hatch = HatchFill::dot_grid().with_angle(0.0).with_spacing(10.0);
+        assert_eq!(count(&hatch.pattern_path(&rect)), 11 * 6);
+    }
+
+    #[test]
+    fn dot_count_is_limited() {
+        // Thin shapes have far more columns than rows.
+        for rect in [
+            kurbo::Rect::new(0.0, 0.0, 1e6, 1.0),
+            kurbo::Rect::new(0.0, 0.0, 1e4, 1e4),
+        ] {
+            for hatch in [HatchFill::dot_grid(), HatchFill::stipple(7)] {
+                let dots = count(&hatch.with_spacing(0.01).pattern_path(&rect));
+                assert!(dots <= MAX_DOTS as usize, "{dots}");
+                assert!(dots > MAX_DOTS as usize / 4, "{dots}");
+            }
+        }
+    }
+
+    #[test]
+    fn non_finite_bounds_are_empty() {
+        let rect = kurbo::Rect::new(0.0, 0.0, f64::INFINITY, 1.0);
+        assert!(HatchFill::dot_grid().pattern_path(&rect).is_empty());
+        assert!(HatchFill::diagonal().pattern_path(&rect).is_empty());
+    }
+
+    #[test]
+    fn fill_rule_coverage() {
+        // Two nested squares wound in the same direction.
+        let mut path = kurbo::Rect::new(0.0, 0.0, 10.0, 10.0).to_path(TOLERANCE);
+        path.extend(kurbo::Rect::new(3.0, 3.0, 7.0, 7.0).to_path(TOLERANCE));
+
+        let hatch = HatchFill::diagonal();
+        let hole = kurbo::Point::new(5.0, 5.0);
+        let ring = kurbo::Point::new(1.0, 5.0);
+        let outside = kurbo::Point::new(-1.0, 5.0);
+
+        assert!(hatch.contains(&path, hole, peniko::Fill::NonZero));
+        assert!(!hatch.contains(&path, hole, peniko::Fill::EvenOdd));
+
+        for fill_rule in [peniko::Fill::NonZero, peniko::Fill::EvenOdd] {
+            assert!(hatch.contains(&path, ring, fill_rule));
+            assert!(!hatch.contains(&path, outside, fill_rule));
+        }
+    }
+}
@@ -1,10 +1,20 @@
 //! Drawing [`HeadVector`] on the border of [`Vector`] shapes.
 
-use bevy_ecs::prelude::*;
-use bevy_math::DVec2;
+use bevy_ecs::{entity::EntityHashSet, prelude::*};
+use bevy_hierarchy::prelude::*;
+use bevy_math::{DVec2, Quat};
+use bevy_transform::prelude::*;
 use bevy_vello::vello::{self, kurbo};
 
-use crate::{Fill, SceneHolder, Stroke, Vector};
+use crate::{
+    brush::{Brush, BrushHolder},
+    clip::{draw_clipped, ClipPath},
+    fill::Fills,
+    paint_order::PaintOrder,
+    stroke::Strokes,
+    vector::{draw_shape, flip_y},
+    Fill, SceneHolder, Stroke, Vector,
+};
 
 /// Prepare [`HeadTransform`]s for drawing [`HeadVector`]s on the border of [`Vector`] shapes.
 #[allow(clippy::type_complexity)]
@@ -22,48 +32,112 @@ pub(super) fn prepare_heads<V: Vector + Component>(
     }
 }
 
+/// Sync the [`Transform`] of [`HeadFollower`]s to the [`HeadTransform`] of their parent.
+pub(super) fn follow_heads(
+    q_heads: Query<Ref<HeadTransform>>,
+    mut q_followers: Query<(Ref<HeadFollower>, Ref<Parent>, &mut Transform)>,
+) {
+    for (follower, parent, mut transform) in q_followers.iter_mut() {
+        let Ok(head_transform) = q_heads.get(parent.get()) else {
+            continue;
+        };
+
+        if !(head_transform.is_changed() || follower.is_changed() || parent.is_changed()) {
+            continue;
+        }
+
+        let [a, b, _, _, x, y] = flip_y(head_transform.0).as_coeffs();
+
+        transform.translation.x = x as f32;
+        transform.translation.y = y as f32;
+
+        if follower.rotation {
+            transform.rotation = Quat::from_rotation_z(f64::atan2(b, a) as f32);
+        }
+
+        if follower.scale {
+            let scale = f64::hypot(a, b) as f32;
+            transform.scale.x = scale;
+            transform.scale.y = scale;
+        }
+    }
+}
+
 /// Draw [`HeadVector`] shapes.
-#[allow(clippy::type_complexity)]
+///
+/// Heads are redrawn when any of their drawing components change or are removed.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub(super) fn draw_heads<V: Vector + Component>(
     mut commands: Commands,
-    q_vectors: Query<
+    q_changed: Query<
+        Entity,
         (
-            Entity,
-            &HeadVector<V>,
-            &HeadTransform,
-            Option<&HeadFill>,
-            Option<&HeadStroke>,
+            With<HeadVector<V>>,
+            Or<(
+                Changed<HeadVector<V>>,
+                Changed<HeadTransform>,
+                Changed<HeadFill>,
+                Changed<HeadStroke>,
+                Changed<HeadFills>,
+                Changed<HeadStrokes>,
+                Changed<PaintOrder>,
+                Changed<ClipPath>,
+            )>,
         ),
-        Or<(
-            Changed<HeadVector<V>>,
-            Changed<HeadTransform>,
-            Changed<Fill>,
-            Changed<Stroke>,
-        )>,
     >,
+    q_vectors: Query<(
+        &HeadVector<V>,
+        &HeadTransform,
+        Option<&HeadFill>,
+        Option<&HeadStroke>,
+        Option<&HeadFills>,
+        Option<&HeadStrokes>,
+        Option<&PaintOrder>,
+        Option<&ClipPath>,
+    )>,
+    mut removed_fill: RemovedComponents<HeadFill>,
+    mut removed_stroke: RemovedComponents<HeadStroke>,
+    mut removed_fills: RemovedComponents<HeadFills>,
+    mut removed_strokes: RemovedComponents<HeadStrokes>,
+    mut removed_paint_orders: RemovedComponents<PaintOrder>,
+    mut removed_clip_paths: RemovedComponents<ClipPath>,
 ) {
-    for (entity, head_vector, head_transform, fill, stroke) in q_vectors.iter() {
-        let mut scene = vello::Scene::new();
+    let entities = q_changed
+        .iter()
+        .chain(removed_fill.read())
+        .chain(removed_stroke.read())
+        .chain(removed_fills.read())
+        .chain(removed_strokes.read())
+        .chain(removed_paint_orders.read())
+        .chain(removed_clip_paths.read())
+        .collect::<EntityHashSet>();
 
-        if let Some(fill) = fill {
-            scene.fill(
-                fill.0.style,
-                head_transform.0,
-                &fill.0.brush.value,
-                Some(fill.0.brush.transform),
-                &head_vector.0.shape(),
-            );
-        }
+    for entity in entities {
+        let Ok((head_vector, head_transform, fill, stroke, fills, strokes, paint_order, clip_path)) =
+            q_vectors.get(entity)
+        else {
+            continue;
+        };
+
+        let mut scene = vello::Scene::new();
 
-        if let Some(stroke) = stroke {
-            scene.stroke(
-                &stroke.0.style,
+        draw_clipped(&mut scene, clip_path, |scene| {
+            draw_shape(
+                scene,
                 head_transform.0,
-                &stroke.0.brush.value,
-                Some(stroke.0.brush.transform),
                 &head_vector.0.shape(),
+                fill.map(|fill| &fill.0)
+                    .into_iter()
+                    .chain(fills.iter().flat_map(|fills| &fills.0 .0)),
+                None,
+                stroke
+                    .map(|stroke| &stroke.0)
+                    .into_iter()
+                    .chain(strokes.iter().flat_map(|strokes| &strokes.0 .0)),
+                None,
+                paint_order.copied().unwrap_or_default(),
             );
-        }
+        });
 
         commands
             .entity(entity)
@@ -74,6 +148,13 @@ pub(super) fn draw_heads<V: Vector + Component>(
 /// Marker struct of a vector scene for [`SceneHolder`].
 pub struct HeadScene;
 
+impl HeadScene {
+    /// Default order of the layer in [`SceneLayers`][SceneLayers].
+    ///
+    /// [SceneLayers]: crate::layer::SceneLayers
+    pub const ORDER: i32 = 100;
+}
+
 /// Bundle of components needed for drawing a [`HeadVector`] on the border of a [`Vector`] shape.
 #[derive(Bundle, Debug, Copy, Clone)]
 pub struct HeadBundle<V: Vector>
@@ -110,6 +191,54 @@ pub struct HeadFill(pub Fill);
 #[derive(Component, Default, Debug, Clone)]
 pub struct HeadStroke(pub Stroke);
 
+/// Multiple fills of a [`HeadVector`].
+#[derive(Component, Default, Debug, Clone)]
+pub struct HeadFills(pub Fills);
+
+/// Multiple strokes of a [`HeadVector`].
+#[derive(Component, Default, Debug, Clone)]
+pub struct HeadStrokes(pub Strokes);
+
+impl BrushHolder for HeadFill {
+    fn brushes(&self) -> impl Iterator<Item = &Brush> {
+        self.0.brushes()
+    }
+
+    fn brushes_mut(&mut self) -> impl Iterator<Item = &mut Brush> {
+        self.0.brushes_mut()
+    }
+}
+
+impl BrushHolder for HeadStroke {
+    fn brushes(&self) -> impl Iterator<Item = &Brush> {
+        self.0.brushes()
+    }
+
+    fn brushes_mut(&mut self) -> impl Iterator<Item = &mut Brush> {
+        self.0.brushes_mut()
+    }
+}
+
+impl BrushHolder for HeadFills {
+    fn brushes(&self) -> impl Iterator<Item = &Brush> {
+        self.0.brushes()
+    }
+
+    fn brushes_mut(&mut self) -> impl Iterator<Item = &mut Brush> {
+        self.0.brushes_mut()
+    }
+}
+
+impl BrushHolder for HeadStrokes {
+    fn brushes(&self) -> impl Iterator<Item = &Brush> {
+        self.0.brushes()
+    }
+
+    fn brushes_mut(&mut self) -> impl Iterator<Item = &mut Brush> {
+        self.0.brushes_mut()
+    }
+}
+
 /// Positioning configurations of a head.
 #[derive(Component, Debug, Clone, Copy)]
 pub struct Head {
@@ -178,3 +307,45 @@ impl HeadTransform {
         self.0
     }
 }
+
+/// Syncs the [`Transform`] of an entity to the [`HeadTransform`] of its parent.
+///
+/// This allows other Bevy content (sprites, text, particle emitters, etc.) to be attached
+/// to the head of a [`Vector`] shape by spawning it as a child of the entity holding the [`Head`].
+/// The z translation of the follower is left untouched.
+///
+/// The transform is synced in the [`FollowHead`][FollowHead] system set.
+///
+/// [FollowHead]: crate::FollowHead
+#[derive(Component, Debug, Clone, Copy)]
+pub struct HeadFollower {
+    /// Follow the rotation of the head.
+    pub rotation: bool,
+    /// Follow the scale of the head.
+    pub scale: bool,
+}
+
+impl Default for HeadFollower {
+    fn default() -> Self {
+        Self {
+            rotation: true,
+            scale: true,
+        }
+    }
+}
+
+impl HeadFollower {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_rotation(mut self, rotation: bool) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn with_scale(mut self, scale: bool) -> Self {
+        self.scale = scale;
+        self
+    }
+}
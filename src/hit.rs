@@ -0,0 +1,103 @@
+//! Hit testing of [`Vector`][Vector] shapes.
+//!
+//! [Vector]: crate::Vector
+
+use bevy_vello::prelude::*;
+use kurbo::{ParamCurveNearest, Shape};
+
+use crate::vector::TOLERANCE;
+
+/// Accuracy of the nearest point search on curves.
+const NEAREST_ACCURACY: f64 = 1e-6;
+
+/// Returns true if the `point` is inside a `shape` based on the `fill_rule`.
+pub fn fill_contains(shape: &impl Shape, point: kurbo::Point, fill_rule: peniko::Fill) -> bool {
+    let winding = shape.winding(point);
+
+    match fill_rule {
+        peniko::Fill::NonZero => winding != 0,
+        peniko::Fill::EvenOdd => winding % 2 != 0,
+    }
+}
+
+/// Returns the distance from the `point` to the nearest point on the outline of a `shape`.
+pub fn distance_to_outline(shape: &impl Shape, point: kurbo::Point) -> f64 {
+    kurbo::segments(shape.path_elements(TOLERANCE))
+        .map(|seg| seg.nearest(point, NEAREST_ACCURACY).distance_sq)
+        .fold(f64::INFINITY, f64::min)
+        .sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two nested squares wound in the same direction.
+    fn nested_squares() -> kurbo::BezPath {
+        let mut path = kurbo::Rect::new(0.0, 0.0, 10.0, 10.0).to_path(TOLERANCE);
+        path.extend(kurbo::Rect::new(3.0, 3.0, 7.0, 7.0).to_path(TOLERANCE));
+        path
+    }
+
+    #[test]
+    fn fill_contains_inside_and_outside() {
+        let rect = kurbo::Rect::new(0.0, 0.0, 10.0, 10.0);
+
+        for fill_rule in [peniko::Fill::NonZero, peniko::Fill::EvenOdd] {
+            assert!(fill_contains(&rect, kurbo::Point::new(5.0, 5.0), fill_rule));
+            assert!(!fill_contains(
+                &rect,
+                kurbo::Point::new(-1.0, 5.0),
+                fill_rule
+            ));
+            assert!(!fill_contains(
+                &rect,
+                kurbo::Point::new(5.0, 11.0),
+                fill_rule
+            ));
+        }
+    }
+
+    #[test]
+    fn fill_contains_fill_rules() {
+        let path = nested_squares();
+        let hole = kurbo::Point::new(5.0, 5.0);
+        let ring = kurbo::Point::new(1.0, 5.0);
+
+        assert!(fill_contains(&path, hole, peniko::Fill::NonZero));
+        assert!(!fill_contains(&path, hole, peniko::Fill::EvenOdd));
+        assert!(fill_contains(&path, ring, peniko::Fill::NonZero));
+        assert!(fill_contains(&path, ring, peniko::Fill::EvenOdd));
+    }
+
+    #[test]
+    fn distance_to_outline_inside_and_outside() {
+        let rect = kurbo::Rect::new(0.0, 0.0, 10.0, 10.0);
+
+        let distance = distance_to_outline(&rect, kurbo::Point::new(5.0, 2.0));
+        assert!((distance - 2.0).abs() < 1e-9);
+
+        let distance = distance_to_outline(&rect, kurbo::Point::new(13.0, 14.0));
+        assert!((distance - 5.0).abs() < 1e-9);
+
+        let distance = distance_to_outline(&rect, kurbo::Point::new(10.0, 4.0));
+        assert!(distance.abs() < 1e-9);
+    }
+
+    #[test]
+    fn distance_to_outline_curves() {
+        let circle = kurbo::Circle::new((0.0, 0.0), 10.0);
+
+        let distance = distance_to_outline(&circle, kurbo::Point::new(0.0, 4.0));
+        assert!((distance - 6.0).abs() < 1e-2);
+
+        let distance = distance_to_outline(&circle, kurbo::Point::new(12.0, 0.0));
+        assert!((distance - 2.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn distance_to_empty_outline() {
+        let distance = distance_to_outline(&kurbo::BezPath::new(), kurbo::Point::ZERO);
+        assert_eq!(distance, f64::INFINITY);
+    }
+}
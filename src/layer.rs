@@ -0,0 +1,215 @@
+//! Ordering of [`SceneHolder`] layers during compositing.
+
+use std::{any::TypeId, sync::Arc};
+
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    entity::{EntityHashMap, EntityHashSet},
+    prelude::*,
+};
+use bevy_utils::HashSet;
+use bevy_vello::prelude::*;
+
+use crate::SceneHolder;
+
+/// Queue entities whose [`SceneHolder<T>`] layer was changed or removed to be recomposited.
+///
+/// All entities with the layer are queued when the [`SceneLayers`] change.
+pub(crate) fn queue_layer_changes<T: Send + Sync + 'static>(
+    layers: Res<SceneLayers>,
+    q_changed: Query<Entity, Changed<SceneHolder<T>>>,
+    q_layers: Query<Entity, With<SceneHolder<T>>>,
+    mut removed: RemovedComponents<SceneHolder<T>>,
+    mut queue: ResMut<CompositeQueue>,
+) {
+    if layers.is_changed() {
+        queue.entities.extend(q_layers.iter());
+    } else {
+        queue.entities.extend(q_changed.iter());
+    }
+
+    queue.entities.extend(removed.read());
+}
+
+/// Collect the [`SceneHolder<T>`] layer scenes of the queued entities.
+pub(crate) fn collect_layer_scenes<T: Send + Sync + 'static>(
+    layers: Res<SceneLayers>,
+    q_layers: Query<&SceneHolder<T>>,
+    mut queue: ResMut<CompositeQueue>,
+) {
+    // Unregistered layers are not composited.
+    let Some(index) = layers.index::<T>() else {
+        return;
+    };
+
+    let CompositeQueue { entities, scenes } = &mut *queue;
+    for entity in entities.iter() {
+        if let Ok(holder) = q_layers.get(*entity) {
+            scenes
+                .entry(*entity)
+                .or_default()
+                .push((index, holder.shared_scene()));
+        }
+    }
+}
+
+/// Extension trait for registering custom [`SceneHolder`] layers to be composited.
+pub trait VelloLayerAppExt {
+    /// Registers a [`SceneHolder<T>`] layer with the given order in [`SceneLayers`],
+    /// or updates its order if it is already registered.
+    ///
+    /// Systems that produce a [`SceneHolder<T>`] should run before the [`Composite`][Composite]
+    /// system set. The [`VelloScene`] of an entity is recomposited whenever any of its layers change.
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_vello_graphics::prelude::*;
+    ///
+    /// /// Marker struct of a selection outline scene for [`SceneHolder`].
+    /// struct OutlineScene;
+    ///
+    /// App::new()
+    ///     .add_plugins(VelloGraphicsPlugin)
+    ///     // Draw outlines above shapes but below heads.
+    ///     .register_vello_layer::<OutlineScene>(50);
+    /// ```
+    ///
+    /// [Composite]: crate::Composite
+    fn register_vello_layer<T: Send + Sync + 'static>(&mut self, order: i32) -> &mut Self;
+}
+
+impl VelloLayerAppExt for App {
+    fn register_vello_layer<T: Send + Sync + 'static>(&mut self, order: i32) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_insert_with(SceneLayers::default)
+            .insert::<T>(order);
+        add_layer_systems::<T>(self);
+        self
+    }
+}
+
+/// Registers a [`SceneHolder<T>`] layer with the given order if it is not registered yet.
+pub(crate) fn init_vello_layer<T: Send + Sync + 'static>(app: &mut App, order: i32) {
+    app.world_mut()
+        .get_resource_or_insert_with(SceneLayers::default)
+        .init::<T>(order);
+    add_layer_systems::<T>(app);
+}
+
+/// Adds the systems that track a [`SceneHolder<T>`] layer for compositing, once per layer.
+fn add_layer_systems<T: Send + Sync + 'static>(app: &mut App) {
+    let mut tracked = app
+        .world_mut()
+        .get_resource_or_insert_with(TrackedLayers::default);
+    if !tracked.0.insert(TypeId::of::<T>()) {
+        return;
+    }
+
+    app.init_resource::<CompositeQueue>().add_systems(
+        Update,
+        (
+            queue_layer_changes::<T>.in_set(QueueComposite),
+            collect_layer_scenes::<T>.in_set(CollectComposite),
+        ),
+    );
+}
+
+/// Ordered registry of [`SceneHolder`] layers that are composited into a [`VelloScene`].
+///
+/// Layers with a lower order are drawn first (below layers with a higher order).
+/// Layers with the same order are drawn in the order they were registered.
+///
+/// By default, [`VectorScene`][VectorScene] and [`HeadScene`][HeadScene] are registered
+/// with their `ORDER` constants.
+///
+/// Only layers registered with [`VelloLayerAppExt::register_vello_layer`] are tracked for changes,
+/// other layers inserted into this resource are ignored during compositing.
+///
+/// [VectorScene]: crate::vector::VectorScene
+/// [HeadScene]: crate::head::HeadScene
+#[derive(Resource, Default)]
+pub struct SceneLayers(Vec<SceneLayer>);
+
+impl SceneLayers {
+    /// Registers a [`SceneHolder<T>`] layer with the given order,
+    /// or updates its order if it is already registered.
+    pub fn insert<T: Send + Sync + 'static>(&mut self, order: i32) {
+        match self
+            .0
+            .iter_mut()
+            .find(|layer| layer.type_id == TypeId::of::<T>())
+        {
+            Some(layer) => layer.order = order,
+            None => self.0.push(SceneLayer::new::<T>(order)),
+        }
+
+        self.0.sort_by_key(|layer| layer.order);
+    }
+
+    /// Registers a [`SceneHolder<T>`] layer with the given order if it is not registered yet.
+    pub fn init<T: Send + Sync + 'static>(&mut self, order: i32) {
+        if self.order::<T>().is_none() {
+            self.insert::<T>(order);
+        }
+    }
+
+    /// Unregisters a [`SceneHolder<T>`] layer, returning its order if it was registered.
+    pub fn remove<T: Send + Sync + 'static>(&mut self) -> Option<i32> {
+        let index = self
+            .0
+            .iter()
+            .position(|layer| layer.type_id == TypeId::of::<T>())?;
+
+        Some(self.0.remove(index).order)
+    }
+
+    /// Returns the order of a [`SceneHolder<T>`] layer if it is registered.
+    pub fn order<T: Send + Sync + 'static>(&self) -> Option<i32> {
+        self.0
+            .iter()
+            .find(|layer| layer.type_id == TypeId::of::<T>())
+            .map(|layer| layer.order)
+    }
+
+    /// Returns the position of a [`SceneHolder<T>`] layer from bottom to top if it is registered.
+    pub(crate) fn index<T: Send + Sync + 'static>(&self) -> Option<usize> {
+        self.0
+            .iter()
+            .position(|layer| layer.type_id == TypeId::of::<T>())
+    }
+}
+
+/// A registered [`SceneHolder`] layer.
+struct SceneLayer {
+    type_id: TypeId,
+    order: i32,
+}
+
+impl SceneLayer {
+    fn new<T: Send + Sync + 'static>(order: i32) -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            order,
+        }
+    }
+}
+
+/// Layers that have systems tracking them for compositing.
+#[derive(Resource, Default)]
+struct TrackedLayers(HashSet<TypeId>);
+
+/// Entities to be recomposited, with the scenes of their layers.
+#[derive(Resource, Default)]
+pub(crate) struct CompositeQueue {
+    pub(crate) entities: EntityHashSet,
+    /// Layer scenes of the queued entities, with the index of their layer.
+    pub(crate) scenes: EntityHashMap<Vec<(usize, Arc<vello::Scene>)>>,
+}
+
+/// System set for queueing entities to be recomposited.
+#[derive(SystemSet, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub(crate) struct QueueComposite;
+
+/// System set for collecting the layer scenes of queued entities.
+#[derive(SystemSet, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub(crate) struct CollectComposite;
@@ -47,39 +47,86 @@
 
 pub use bevy_vello;
 
-use std::marker::PhantomData;
+use std::{marker::PhantomData, sync::Arc};
 
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
+use bevy_hierarchy::prelude::*;
 use bevy_vello::prelude::*;
 
-use head::{draw_heads, prepare_heads, HeadScene};
+use blend::draw_blended;
+use boolean::{evaluate_booleans, sync_boolean_operands};
+use bounds::{compute_bounds, compute_head_bounds};
+use brush::{sync_image_brushes, update_image_brush_cache, ImageBrushCache};
+use clip::prepare_clips;
+use group::{composite_groups, queue_groups, CompositeScene, GroupQueue};
+use head::{draw_heads, follow_heads, prepare_heads, HeadScene};
+use layer::{init_vello_layer, CollectComposite, CompositeQueue, QueueComposite};
+use offset::evaluate_offsets;
 use prelude::*;
+use shadow::{draw_shadows, ShadowScene};
+use stroke::animate_dashes;
+use style::apply_styles;
 use vector::{draw_vectors, VectorScene};
 
 pub mod bezpath;
+pub mod blend;
+pub mod boolean;
+pub mod bounds;
 pub mod brush;
 pub mod circle;
+pub mod clip;
 pub mod fill;
+pub mod group;
+pub mod hatch;
 pub mod head;
+pub mod hit;
+pub mod layer;
 pub mod line;
+pub mod measure;
+pub mod offset;
+pub mod paint_order;
+#[cfg(feature = "picking")]
+pub mod picking;
 pub mod rect;
+pub mod shadow;
 pub mod stroke;
+pub mod style;
+pub mod variable_stroke;
 pub mod vector;
 
 pub mod prelude {
     pub use crate::{
         bezpath::VelloBezPath,
-        brush::Brush,
+        blend::{BlendMode, Opacity},
+        boolean::{BooleanOp, VelloBoolean},
+        bounds::VectorBounds,
+        brush::{Brush, BrushUnits, ImageBrush, InterpolationSpace},
         circle::VelloCircle,
-        fill::Fill,
-        head::{Head, HeadBundle, HeadFill, HeadStroke, HeadTransform, HeadVector},
+        clip::Clip,
+        fill::{Fill, Fills},
+        group::VelloGroup,
+        hatch::{HatchFill, HatchPattern},
+        head::{
+            Head, HeadBundle, HeadFill, HeadFills, HeadFollower, HeadStroke, HeadStrokes,
+            HeadTransform, HeadVector,
+        },
+        layer::VelloLayerAppExt,
         line::VelloLine,
+        measure::PathMeasure,
+        offset::VelloOffset,
+        paint_order::PaintOrder,
         rect::VelloRect,
-        stroke::Stroke,
+        shadow::Shadow,
+        stroke::{DashAnimation, Stroke, StrokeAlignment, Strokes},
+        style::{StyleKey, VelloStyle, VelloStyles},
+        variable_stroke::{VariableStroke, WidthProfile},
         vector::Vector,
         VelloGraphicsPlugin,
     };
+
+    #[cfg(feature = "picking")]
+    pub use crate::picking::{VectorPickingPlugin, VelloPickingPlugin};
 }
 
 /// A plugin that automates the pipeline of drawing and compositing vello shapes.
@@ -87,16 +134,96 @@ pub struct VelloGraphicsPlugin;
 
 impl Plugin for VelloGraphicsPlugin {
     fn build(&self, app: &mut App) {
-        app.configure_sets(Update, ((DrawVector, DrawHead), Composite).chain());
+        app.configure_sets(
+            Update,
+            ((DrawVector, DrawHead), Composite, CompositeGroup).chain(),
+        );
+        app.configure_sets(
+            Update,
+            (QueueComposite, CollectComposite).chain().in_set(Composite),
+        );
         app.configure_sets(Update, (PrepareHead, DrawHead).chain());
+        app.configure_sets(Update, (PrepareHead, FollowHead).chain());
+        app.configure_sets(Update, (PrepareClip, (DrawVector, DrawHead)).chain());
+        app.configure_sets(Update, (PrepareBrush, (DrawVector, DrawHead)).chain());
+        app.configure_sets(
+            Update,
+            (PrepareHead, ComputeBounds, ComputeHeadBounds).chain(),
+        );
+        app.configure_sets(
+            Update,
+            (
+                PrepareBoolean,
+                EvaluateBoolean,
+                EvaluateOffset,
+                (PrepareHead, PrepareClip, DrawVector, ComputeBounds),
+            )
+                .chain(),
+        );
+
+        init_vello_layer::<ShadowScene>(app, ShadowScene::ORDER);
+        init_vello_layer::<VectorScene>(app, VectorScene::ORDER);
+        init_vello_layer::<HeadScene>(app, HeadScene::ORDER);
 
         app.add_plugins((
             VectorPlugin::<VelloRect>::default(),
             VectorPlugin::<VelloCircle>::default(),
             VectorPlugin::<VelloLine>::default(),
             VectorPlugin::<VelloBezPath>::default(),
+            VectorPlugin::<VelloBoolean>::default(),
+            VectorPlugin::<VelloOffset<VelloRect>>::default(),
+            VectorPlugin::<VelloOffset<VelloCircle>>::default(),
+            VectorPlugin::<VelloOffset<VelloLine>>::default(),
+            VectorPlugin::<VelloOffset<VelloBezPath>>::default(),
         ))
-        .add_systems(Update, composite.in_set(Composite));
+        .add_systems(Update, queue_composite.in_set(QueueComposite))
+        .add_systems(Update, composite.in_set(Composite).after(CollectComposite))
+        .init_resource::<GroupQueue>()
+        .add_systems(
+            Update,
+            (queue_groups, composite_groups)
+                .chain()
+                .in_set(CompositeGroup),
+        )
+        .add_systems(Update, follow_heads.in_set(FollowHead))
+        .add_systems(Update, evaluate_booleans.in_set(EvaluateBoolean))
+        .add_systems(
+            Update,
+            (
+                evaluate_offsets::<VelloRect>,
+                evaluate_offsets::<VelloCircle>,
+                evaluate_offsets::<VelloLine>,
+                evaluate_offsets::<VelloBezPath>,
+            )
+                .in_set(EvaluateOffset),
+        )
+        .add_systems(Update, animate_dashes.before(DrawVector))
+        .init_resource::<VelloStyles>()
+        .add_systems(
+            Update,
+            apply_styles.before(PrepareBrush).before(ComputeBounds),
+        )
+        .init_resource::<ImageBrushCache>()
+        .add_systems(
+            Update,
+            (
+                update_image_brush_cache,
+                (
+                    sync_image_brushes::<Fill>,
+                    sync_image_brushes::<Stroke>,
+                    sync_image_brushes::<Fills>,
+                    sync_image_brushes::<Strokes>,
+                    sync_image_brushes::<HatchFill>,
+                    sync_image_brushes::<VariableStroke>,
+                    sync_image_brushes::<HeadFill>,
+                    sync_image_brushes::<HeadStroke>,
+                    sync_image_brushes::<HeadFills>,
+                    sync_image_brushes::<HeadStrokes>,
+                ),
+            )
+                .chain()
+                .in_set(PrepareBrush),
+        );
     }
 }
 
@@ -110,50 +237,107 @@ where
 {
     fn build(&self, app: &mut App) {
         app.add_systems(Update, draw_vectors::<V>.in_set(DrawVector))
+            .add_systems(Update, draw_shadows::<V>.in_set(DrawVector))
             .add_systems(Update, draw_heads::<V>.in_set(DrawHead))
-            .add_systems(Update, prepare_heads::<V>.in_set(PrepareHead));
+            .add_systems(Update, prepare_heads::<V>.in_set(PrepareHead))
+            .add_systems(Update, prepare_clips::<V>.in_set(PrepareClip))
+            .add_systems(Update, sync_boolean_operands::<V>.in_set(PrepareBoolean))
+            .add_systems(Update, compute_bounds::<V>.in_set(ComputeBounds))
+            .add_systems(Update, compute_head_bounds::<V>.in_set(ComputeHeadBounds));
     }
 }
 
+/// Queue entities whose [`Opacity`], [`BlendMode`] or [`VelloGroup`] was changed or removed
+/// to be recomposited.
+#[allow(clippy::type_complexity)]
+fn queue_composite(
+    q_changed: Query<Entity, Or<(Changed<Opacity>, Changed<BlendMode>, Changed<VelloGroup>)>>,
+    mut removed_opacities: RemovedComponents<Opacity>,
+    mut removed_blend_modes: RemovedComponents<BlendMode>,
+    mut removed_groups: RemovedComponents<VelloGroup>,
+    mut queue: ResMut<CompositeQueue>,
+) {
+    queue.entities.extend(q_changed.iter());
+    queue.entities.extend(removed_opacities.read());
+    queue.entities.extend(removed_blend_modes.read());
+    queue.entities.extend(removed_groups.read());
+}
+
+/// Composite the [`SceneLayers`][SceneLayers] of queued entities into their [`VelloScene`],
+/// applying their [`Opacity`] and [`BlendMode`].
+///
+/// Entities without a [`VelloScene`] and [`VelloGroup`]s are composited into a
+/// [`SceneHolder<CompositeScene>`] instead, for [`composite_groups`] to pick up.
+///
+/// [SceneLayers]: layer::SceneLayers
 #[allow(clippy::type_complexity)]
 fn composite(
     mut commands: Commands,
-    q_scenes: Query<
+    mut queue: ResMut<CompositeQueue>,
+    q_entities: Query<
         (
-            Entity,
-            &SceneHolder<VectorScene>,
-            Option<&SceneHolder<HeadScene>>,
+            Has<VelloScene>,
+            Has<VelloGroup>,
+            Option<&Opacity>,
+            Option<&BlendMode>,
         ),
-        Or<(
-            Changed<SceneHolder<VectorScene>>,
-            Changed<SceneHolder<HeadScene>>,
-        )>,
+        Or<(With<VelloScene>, With<Parent>)>,
     >,
 ) {
-    for (entity, vector_scene, head_scene) in q_scenes.iter() {
+    let CompositeQueue { entities, scenes } = &mut *queue;
+
+    // Entities without any layer left are composited into an empty scene.
+    for entity in entities.drain() {
+        let mut layer_scenes = scenes.remove(&entity).unwrap_or_default();
+        let Ok((has_scene, is_group, opacity, blend_mode)) = q_entities.get(entity) else {
+            continue;
+        };
+
+        let is_standalone = has_scene && !is_group;
+
+        // Opacity and blend mode of groups apply to the entire group.
+        let opacity = opacity.filter(|_| !is_group);
+        let blend_mode = blend_mode.filter(|_| !is_group);
+
+        // Draw layers from bottom to top.
+        layer_scenes.sort_by_key(|(index, _)| *index);
+
         let mut scene = vello::Scene::new();
-        scene.append(vector_scene.scene(), None);
+        draw_blended(&mut scene, opacity, blend_mode, |scene| {
+            for (_, layer_scene) in layer_scenes.iter() {
+                scene.append(layer_scene, None);
+            }
+        });
 
-        if let Some(head_scene) = head_scene {
-            scene.append(head_scene.scene(), None);
+        if is_standalone {
+            commands.entity(entity).insert(VelloScene::from(scene));
+        } else {
+            commands
+                .entity(entity)
+                .insert(SceneHolder::<CompositeScene>::new(scene));
         }
-
-        commands.entity(entity).insert(VelloScene::from(scene));
     }
 }
 
 /// A read-only holder of [`vello::Scene`].
+///
+/// The scene is shared, so that compositing does not copy it.
 #[derive(Component, Default, Clone)]
-pub struct SceneHolder<T>(vello::Scene, PhantomData<T>);
+pub struct SceneHolder<T>(Arc<vello::Scene>, PhantomData<T>);
 
 impl<T> SceneHolder<T> {
     pub fn new(scene: vello::Scene) -> Self {
-        Self(scene, PhantomData)
+        Self(Arc::new(scene), PhantomData)
     }
 
     pub fn scene(&self) -> &vello::Scene {
         &self.0
     }
+
+    /// Returns a shared reference to the scene.
+    pub(crate) fn shared_scene(&self) -> Arc<vello::Scene> {
+        self.0.clone()
+    }
 }
 
 /// System set for drawing vector shape.
@@ -164,10 +348,46 @@ pub struct DrawVector;
 #[derive(SystemSet, Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct PrepareHead;
 
+/// System set for syncing [`HeadFollower`]s to their parent's head.
+#[derive(SystemSet, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct FollowHead;
+
+/// System set for preparing [`Clip`] paths.
+#[derive(SystemSet, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct PrepareClip;
+
+/// System set for resolving [`ImageBrush`]es.
+#[derive(SystemSet, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct PrepareBrush;
+
+/// System set for syncing the operands of [`VelloBoolean`]s.
+#[derive(SystemSet, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct PrepareBoolean;
+
+/// System set for evaluating [`VelloBoolean`]s.
+#[derive(SystemSet, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct EvaluateBoolean;
+
+/// System set for evaluating [`VelloOffset`]s.
+#[derive(SystemSet, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct EvaluateOffset;
+
+/// System set for computing [`VectorBounds`] of vector shapes.
+#[derive(SystemSet, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct ComputeBounds;
+
+/// System set for computing the head part of [`VectorBounds`].
+#[derive(SystemSet, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct ComputeHeadBounds;
+
 /// System set for drawing vector shape's head.
 #[derive(SystemSet, Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct DrawHead;
 
-/// System set for compositing all [`SceneHolder`]s into [`VelloScene`].
+/// System set for compositing all [`SceneHolder`]s into [`VelloScene`] based on [`SceneLayers`].
 #[derive(SystemSet, Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct Composite;
+
+/// System set for compositing [`VelloGroup`] members into the group's [`VelloScene`].
+#[derive(SystemSet, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct CompositeGroup;
@@ -0,0 +1,337 @@
+//! Measuring lengths, positions and directions along the outline of shapes.
+
+use bevy_vello::vello::kurbo;
+use kurbo::{ParamCurve, ParamCurveArclen, ParamCurveDeriv, ParamCurveNearest, Shape};
+
+use crate::vector::TOLERANCE;
+
+/// Accuracy of arc length and nearest point computations.
+const ACCURACY: f64 = 1e-6;
+
+/// Arc length measurement of the outline of a shape.
+///
+/// Measuring is done once on creation,
+/// so that multiple queries can be made along the outline without measuring it again.
+/// All lengths are clamped within `[0.0, length]`.
+#[derive(Default, Debug, Clone)]
+pub struct PathMeasure {
+    segments: Vec<MeasuredSegment>,
+    length: f64,
+}
+
+impl PathMeasure {
+    pub fn new(shape: &impl Shape) -> Self {
+        let mut segments = Vec::new();
+        let mut length = 0.0;
+
+        for (index, segment) in shape.path_segments(TOLERANCE).enumerate() {
+            let segment_length = segment.arclen(ACCURACY);
+            // Zero length segments have no position along the outline.
+            if segment_length <= 0.0 {
+                continue;
+            }
+
+            segments.push(MeasuredSegment {
+                segment,
+                index,
+                start: length,
+                length: segment_length,
+            });
+            length += segment_length;
+        }
+
+        Self { segments, length }
+    }
+
+    /// Total length of the outline.
+    pub fn length(&self) -> f64 {
+        self.length
+    }
+
+    /// Returns the segment at a `length` along the outline,
+    /// as its index in [`kurbo::BezPath::segments`] and the parameter `t` on the segment.
+    pub fn segment_at_length(&self, length: f64) -> Option<(usize, f64)> {
+        let (measured, t) = self.locate(length)?;
+        Some((measured.index, t))
+    }
+
+    /// Returns the point at a `length` along the outline.
+    pub fn point_at_length(&self, length: f64) -> Option<kurbo::Point> {
+        let (measured, t) = self.locate(length)?;
+        Some(measured.segment.eval(t))
+    }
+
+    /// Returns the unit tangent at a `length` along the outline, in the direction of the outline.
+    pub fn tangent_at_length(&self, length: f64) -> Option<kurbo::Vec2> {
+        let (measured, t) = self.locate(length)?;
+        Some(tangent(measured.segment, t))
+    }
+
+    /// Returns the unit normal at a `length` along the outline,
+    /// which is the tangent rotated by 90 degrees counterclockwise in a y-up space.
+    pub fn normal_at_length(&self, length: f64) -> Option<kurbo::Vec2> {
+        let tangent = self.tangent_at_length(length)?;
+        Some(kurbo::Vec2::new(-tangent.y, tangent.x))
+    }
+
+    /// Returns the signed curvature (inverse of the radius) at a `length` along the outline.
+    ///
+    /// The curvature is positive when the outline turns towards its normal.
+    pub fn curvature_at_length(&self, length: f64) -> Option<f64> {
+        let (measured, t) = self.locate(length)?;
+        let (d1, d2) = derivatives(measured.segment, t);
+
+        let speed = d1.hypot();
+        if speed <= f64::EPSILON {
+            return Some(0.0);
+        }
+
+        Some(d1.cross(d2) / (speed * speed * speed))
+    }
+
+    /// Returns the point on the outline that is nearest to a `point`.
+    pub fn nearest(&self, point: kurbo::Point) -> Option<NearestPoint> {
+        let (measured, nearest) = self
+            .segments
+            .iter()
+            .map(|measured| (measured, measured.segment.nearest(point, ACCURACY)))
+            .min_by(|(_, a), (_, b)| a.distance_sq.total_cmp(&b.distance_sq))?;
+
+        Some(NearestPoint {
+            point: measured.segment.eval(nearest.t),
+            distance: nearest.distance_sq.sqrt(),
+            length: measured.start + measured.segment.subsegment(0.0..nearest.t).arclen(ACCURACY),
+            segment: measured.index,
+        })
+    }
+
+    /// Finds the measured segment at a `length` and the parameter `t` on it.
+    fn locate(&self, length: f64) -> Option<(&MeasuredSegment, f64)> {
+        let length = length.clamp(0.0, self.length);
+        let index = self
+            .segments
+            .partition_point(|measured| measured.start + measured.length < length)
+            .min(self.segments.len().checked_sub(1)?);
+
+        let measured = &self.segments[index];
+        let local = (length - measured.start).clamp(0.0, measured.length);
+        let t = if local >= measured.length {
+            1.0
+        } else {
+            measured.segment.inv_arclen(local, ACCURACY)
+        };
+
+        Some((measured, t))
+    }
+}
+
+/// A point on the outline of a shape nearest to another point, from [`PathMeasure::nearest`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NearestPoint {
+    /// Position of the nearest point.
+    pub point: kurbo::Point,
+    /// Distance from the queried point.
+    pub distance: f64,
+    /// Length along the outline up to the nearest point.
+    pub length: f64,
+    /// Index of the segment in [`kurbo::BezPath::segments`].
+    pub segment: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct MeasuredSegment {
+    segment: kurbo::PathSeg,
+    /// Index of the segment in [`kurbo::BezPath::segments`].
+    index: usize,
+    /// Length along the outline at the start of the segment.
+    start: f64,
+    length: f64,
+}
+
+/// Unit tangent of a `segment` at parameter `t`.
+///
+/// At cusps where the derivative vanishes, the direction between nearby points is used instead.
+pub(crate) fn tangent(segment: kurbo::PathSeg, t: f64) -> kurbo::Vec2 {
+    let (d1, _) = derivatives(segment, t);
+    if d1.hypot2() > f64::EPSILON {
+        return d1.normalize();
+    }
+
+    let delta = 1e-6;
+    let chord = segment.eval(f64::min(t + delta, 1.0)) - segment.eval(f64::max(t - delta, 0.0));
+    if chord.hypot2() > 0.0 {
+        chord.normalize()
+    } else {
+        (segment.end() - segment.start()).normalize()
+    }
+}
+
+/// First and second derivatives of a `segment` at parameter `t`.
+fn derivatives(segment: kurbo::PathSeg, t: f64) -> (kurbo::Vec2, kurbo::Vec2) {
+    match segment {
+        kurbo::PathSeg::Line(line) => (line.p1 - line.p0, kurbo::Vec2::ZERO),
+        kurbo::PathSeg::Quad(quad) => {
+            let deriv = quad.deriv();
+            (deriv.eval(t).to_vec2(), deriv.deriv().eval(t).to_vec2())
+        }
+        kurbo::PathSeg::Cubic(cubic) => {
+            let deriv = cubic.deriv();
+            (deriv.eval(t).to_vec2(), deriv.deriv().eval(t).to_vec2())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    use super::*;
+
+    fn assert_close(a: f64, b: f64, epsilon: f64) {
+        assert!((a - b).abs() <= epsilon, "{a} != {b}");
+    }
+
+    fn assert_point_close(a: kurbo::Point, b: kurbo::Point, epsilon: f64) {
+        assert!(a.distance(b) <= epsilon, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn empty_path() {
+        let measure = PathMeasure::new(&kurbo::BezPath::new());
+
+        assert_eq!(measure.length(), 0.0);
+        assert_eq!(measure.point_at_length(0.0), None);
+        assert_eq!(measure.segment_at_length(0.0), None);
+        assert_eq!(measure.nearest(kurbo::Point::ZERO), None);
+    }
+
+    #[test]
+    fn rect_positions() {
+        let measure = PathMeasure::new(&kurbo::Rect::new(0.0, 0.0, 10.0, 5.0));
+        assert_close(measure.length(), 30.0, 1e-9);
+
+        assert_point_close(
+            measure.point_at_length(12.0).unwrap(),
+            kurbo::Point::new(10.0, 2.0),
+            1e-9,
+        );
+        assert_eq!(measure.segment_at_length(12.0), Some((1, 0.4)));
+
+        // The closing segment returns to the start.
+        assert_point_close(
+            measure.point_at_length(28.0).unwrap(),
+            kurbo::Point::new(0.0, 2.0),
+            1e-9,
+        );
+        assert_eq!(measure.segment_at_length(28.0).unwrap().0, 3);
+
+        // Lengths are clamped.
+        assert_point_close(
+            measure.point_at_length(-1.0).unwrap(),
+            kurbo::Point::ZERO,
+            1e-9,
+        );
+        assert_point_close(
+            measure.point_at_length(100.0).unwrap(),
+            kurbo::Point::ZERO,
+            1e-9,
+        );
+    }
+
+    #[test]
+    fn rect_directions() {
+        let measure = PathMeasure::new(&kurbo::Rect::new(0.0, 0.0, 10.0, 5.0));
+
+        let tangent = measure.tangent_at_length(12.0).unwrap();
+        assert_close(tangent.angle(), FRAC_PI_2, 1e-9);
+
+        let normal = measure.normal_at_length(12.0).unwrap();
+        assert_close(normal.angle(), PI, 1e-9);
+
+        assert_eq!(measure.curvature_at_length(12.0), Some(0.0));
+    }
+
+    #[test]
+    fn circle_measurements() {
+        let radius = 4.0;
+        let measure = PathMeasure::new(&kurbo::Circle::new((0.0, 0.0), radius));
+        assert_close(measure.length(), 2.0 * PI * radius, 1e-3);
+
+        for i in 0..16 {
+            let length = measure.length() * i as f64 / 16.0;
+            let point = measure.point_at_length(length).unwrap();
+            assert_close(point.to_vec2().hypot(), radius, 1e-3);
+
+            // The tangent is perpendicular to the radius.
+            let tangent = measure.tangent_at_length(length).unwrap();
+            assert_close(tangent.dot(point.to_vec2()), 0.0, 1e-2);
+            assert_close(tangent.hypot(), 1.0, 1e-9);
+
+            let curvature = measure.curvature_at_length(length).unwrap();
+            assert_close(curvature.abs(), 1.0 / radius, 1e-2);
+        }
+    }
+
+    #[test]
+    fn cubic_arclen_is_uniform() {
+        let mut path = kurbo::BezPath::new();
+        path.move_to((0.0, 0.0));
+        path.curve_to((0.0, 10.0), (20.0, 10.0), (20.0, 0.0));
+
+        let measure = PathMeasure::new(&path);
+        let count = 8;
+        let step = measure.length() / count as f64;
+
+        // Points evenly spaced along the length measure the same length between them.
+        let points = (0..=count)
+            .map(|i| measure.point_at_length(step * i as f64).unwrap())
+            .collect::<Vec<_>>();
+        for pair in points.windows(2) {
+            let chord = kurbo::Line::new(pair[0], pair[1]).arclen(ACCURACY);
+            assert!(chord <= step + 1e-6);
+            assert!(chord >= step * 0.95);
+        }
+    }
+
+    #[test]
+    fn nearest_point() {
+        let measure = PathMeasure::new(&kurbo::Rect::new(0.0, 0.0, 10.0, 5.0));
+
+        let nearest = measure.nearest(kurbo::Point::new(4.0, -3.0)).unwrap();
+        assert_point_close(nearest.point, kurbo::Point::new(4.0, 0.0), 1e-9);
+        assert_close(nearest.distance, 3.0, 1e-9);
+        assert_close(nearest.length, 4.0, 1e-9);
+        assert_eq!(nearest.segment, 0);
+
+        let nearest = measure.nearest(kurbo::Point::new(5.0, 4.0)).unwrap();
+        assert_point_close(nearest.point, kurbo::Point::new(5.0, 5.0), 1e-9);
+        assert_close(nearest.length, 20.0, 1e-9);
+        assert_eq!(nearest.segment, 2);
+    }
+
+    #[test]
+    fn zero_length_segments_keep_indices() {
+        let mut path = kurbo::BezPath::new();
+        path.move_to((0.0, 0.0));
+        path.line_to((0.0, 0.0));
+        path.line_to((10.0, 0.0));
+
+        let measure = PathMeasure::new(&path);
+        assert_close(measure.length(), 10.0, 1e-9);
+        assert_eq!(measure.segment_at_length(5.0), Some((1, 0.5)));
+        assert_close(measure.tangent_at_length(0.0).unwrap().x, 1.0, 1e-9);
+    }
+
+    #[test]
+    fn cusp_tangent() {
+        let segment = kurbo::PathSeg::Cubic(kurbo::CubicBez::new(
+            (0.0, 0.0),
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+        ));
+
+        let tangent = tangent(segment, 0.0);
+        assert_close(tangent.x, 1.0, 1e-6);
+    }
+}
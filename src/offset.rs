@@ -0,0 +1,159 @@
+//! Offsetting (insetting and outsetting) [`Vector`] shapes.
+
+use bevy_ecs::prelude::*;
+use bevy_vello::prelude::*;
+
+use crate::{
+    bezpath::VelloBezPath,
+    boolean::{boolean_path, close_subpaths, BooleanOp},
+    vector::TOLERANCE,
+    Vector,
+};
+
+/// Evaluate the offset paths of [`VelloOffset`]s that have changed.
+pub(super) fn evaluate_offsets<V: Vector + Send + Sync + 'static>(
+    mut q_offsets: Query<&mut VelloOffset<V>, Changed<VelloOffset<V>>>,
+) {
+    for mut offset in q_offsets.iter_mut() {
+        let path = offset_path(&offset.vector.shape(), offset.distance, offset.join);
+        offset.path = path;
+    }
+}
+
+/// A [`Vector`] with its outline offset outwards (positive distance) or inwards (negative distance).
+///
+/// The offset path is evaluated once and cached, and only re-evaluated when the component changes.
+/// Evaluating expands the outline into a stroke and combines it with the shape in a boolean
+/// operation, which is costly for shapes with many curves, so avoid changing it every frame.
+/// Use [`offset_path`] to offset a shape outside of the ECS.
+#[derive(Component, Debug, Clone)]
+pub struct VelloOffset<V: Vector + Send + Sync + 'static> {
+    /// Vector to be offset.
+    pub vector: V,
+    /// Distance to offset the outline by.
+    pub distance: f64,
+    /// Join style of the corners that are offset outwards.
+    pub join: kurbo::Join,
+    path: kurbo::BezPath,
+}
+
+impl<V: Vector + Send + Sync + 'static> VelloOffset<V> {
+    pub fn new(vector: V, distance: f64) -> Self {
+        Self {
+            vector,
+            distance,
+            join: kurbo::Join::Round,
+            path: kurbo::BezPath::new(),
+        }
+    }
+
+    pub fn with_distance(mut self, distance: f64) -> Self {
+        self.distance = distance;
+        self
+    }
+
+    pub fn with_join(mut self, join: kurbo::Join) -> Self {
+        self.join = join;
+        self
+    }
+
+    /// Returns the evaluated path.
+    pub fn path(&self) -> &kurbo::BezPath {
+        &self.path
+    }
+}
+
+impl<V: Vector + Default + Send + Sync + 'static> Default for VelloOffset<V> {
+    fn default() -> Self {
+        Self::new(V::default(), 0.0)
+    }
+}
+
+impl<V: Vector + Send + Sync + 'static> Vector for VelloOffset<V> {
+    fn shape(&self) -> impl kurbo::Shape {
+        self.path.clone()
+    }
+}
+
+impl VelloBezPath {
+    /// Offsets the entire path outwards (positive distance) or inwards (negative distance).
+    pub fn offset(&self, distance: f64, join: kurbo::Join) -> Self {
+        Self::new().with_path(offset_path(&self.path, distance, join))
+    }
+}
+
+/// Offsets the outline of a filled shape outwards (positive `distance`)
+/// or inwards (negative `distance`).
+///
+/// Open subpaths are treated as closed, as they are when filled. The resulting path is flattened,
+/// so it only consists of lines.
+pub fn offset_path(shape: &impl kurbo::Shape, distance: f64, join: kurbo::Join) -> kurbo::BezPath {
+    let path = close_subpaths(shape.path_elements(TOLERANCE));
+    if distance == 0.0 {
+        return path;
+    }
+
+    // The stroke covers the band of the given distance on both sides of the outline.
+    let style = kurbo::Stroke::new(distance.abs() * 2.0).with_join(join);
+    let band = kurbo::stroke(
+        path.iter(),
+        &style,
+        &kurbo::StrokeOpts::default(),
+        TOLERANCE,
+    );
+
+    if distance > 0.0 {
+        boolean_path(&path, &band, BooleanOp::Union)
+    } else {
+        boolean_path(&path, &band, BooleanOp::Difference)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use kurbo::Shape;
+
+    use super::*;
+    use crate::rect::VelloRect;
+
+    #[test]
+    fn path_is_cached() {
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(evaluate_offsets::<VelloRect>);
+
+        let rect = VelloRect::new(10.0, 10.0);
+        let entity = world.spawn(VelloOffset::new(rect, 2.0)).id();
+        schedule.run(&mut world);
+
+        let offset = world.get::<VelloOffset<VelloRect>>(entity).unwrap();
+        let outset = offset_path(&rect.shape(), 2.0, kurbo::Join::Round);
+        assert_eq!(offset.path(), &outset);
+        assert_eq!(offset.shape().to_path(TOLERANCE), outset);
+        assert!(outset.area().abs() > 100.0);
+
+        // Unchanged offsets are not evaluated again.
+        let evaluated = |world: &World| {
+            world
+                .entity(entity)
+                .get_ref::<VelloOffset<VelloRect>>()
+                .unwrap()
+                .last_changed()
+        };
+        let tick = evaluated(&world);
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        assert_eq!(evaluated(&world), tick);
+
+        world
+            .get_mut::<VelloOffset<VelloRect>>(entity)
+            .unwrap()
+            .distance = -2.0;
+        schedule.run(&mut world);
+
+        let offset = world.get::<VelloOffset<VelloRect>>(entity).unwrap();
+        let inset = offset_path(&rect.shape(), -2.0, kurbo::Join::Round);
+        assert_eq!(offset.path(), &inset);
+        assert!((inset.area().abs() - 36.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,20 @@
+//! SVG-style paint order of fills and strokes.
+
+use bevy_ecs::prelude::*;
+
+/// Order in which the fills and strokes of a [`Vector`][Vector] and its
+/// [`HeadVector`][HeadVector] are drawn.
+///
+/// Drawing the stroke behind the fill is useful for outlined text-like shapes and labels,
+/// as only the outer half of the stroke remains visible.
+///
+/// [Vector]: crate::Vector
+/// [HeadVector]: crate::head::HeadVector
+#[derive(Component, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaintOrder {
+    /// Draw fills first, followed by strokes on top.
+    #[default]
+    FillStroke,
+    /// Draw strokes first, followed by fills on top.
+    StrokeFill,
+}
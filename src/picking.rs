@@ -0,0 +1,209 @@
+//! A [`bevy_picking`] backend for [`Vector`] shapes.
+
+use std::marker::PhantomData;
+
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_math::FloatOrd;
+use bevy_picking::backend::prelude::*;
+use bevy_render::prelude::*;
+use bevy_transform::prelude::*;
+use bevy_vello::prelude::*;
+use bevy_window::PrimaryWindow;
+
+use crate::{
+    bezpath::VelloBezPath, boolean::VelloBoolean, circle::VelloCircle, clip::ClipPath, fill::Fills,
+    hatch::HatchFill, hit, line::VelloLine, rect::VelloRect, stroke::Strokes,
+    variable_stroke::VariableStroke, Fill, Stroke, Vector,
+};
+
+/// A plugin that enables picking of the built-in [`Vector`] shapes,
+/// so that `Pointer` events are fired on them.
+///
+/// Points are hit tested against the painted [`Fill`]s, [`HatchFill`] (as a solid area),
+/// [`Stroke`]s and [`VariableStroke`], and points clipped away by a [`ClipPath`] are ignored.
+/// [`Stroke`]s are tested as if they had round joins and caps without dashes (see
+/// [`Stroke::contains`]), so the tips of miter joins and square caps are not pickable,
+/// while the gaps between dashes are.
+///
+/// Use [`VectorPickingPlugin`] to enable picking of custom [`Vector`] shapes.
+pub struct VelloPickingPlugin;
+
+impl Plugin for VelloPickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            VectorPickingPlugin::<VelloRect>::default(),
+            VectorPickingPlugin::<VelloCircle>::default(),
+            VectorPickingPlugin::<VelloLine>::default(),
+            VectorPickingPlugin::<VelloBezPath>::default(),
+            VectorPickingPlugin::<VelloBoolean>::default(),
+        ));
+    }
+}
+
+/// A plugin for picking [`Vector`] shapes.
+#[derive(Default)]
+pub struct VectorPickingPlugin<V: Vector + Component>(PhantomData<V>);
+
+impl<V: Vector + Component> Plugin for VectorPickingPlugin<V>
+where
+    V: Default,
+{
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreUpdate, pick_vectors::<V>.in_set(PickSet::Backend));
+    }
+}
+
+/// Hit test [`Vector`] shapes against all pointers, based on their [`Fill`]s and [`Stroke`]s.
+///
+/// See [`VelloPickingPlugin`] for what counts as a hit.
+#[allow(clippy::type_complexity)]
+fn pick_vectors<V: Vector + Component>(
+    q_pointers: Query<(&PointerId, &PointerLocation)>,
+    q_cameras: Query<(Entity, &Camera, &GlobalTransform)>,
+    q_primary_window: Query<Entity, With<PrimaryWindow>>,
+    q_vectors: Query<(
+        Entity,
+        &V,
+        &GlobalTransform,
+        Option<&Fill>,
+        Option<&Stroke>,
+        Option<&Fills>,
+        Option<&Strokes>,
+        Option<&HatchFill>,
+        Option<&VariableStroke>,
+        Option<&ClipPath>,
+        Option<&PickingBehavior>,
+    )>,
+    mut output: EventWriter<PointerHits>,
+) {
+    let primary_window = q_primary_window.get_single().ok();
+
+    // Front to back.
+    let mut vectors = q_vectors
+        .iter()
+        .filter(|(_, _, transform, ..)| !transform.affine().is_nan())
+        .collect::<Vec<_>>();
+    vectors.sort_by_key(|(_, _, transform, ..)| {
+        std::cmp::Reverse(FloatOrd(transform.translation().z))
+    });
+
+    for (pointer, location) in q_pointers
+        .iter()
+        .filter_map(|(pointer, location)| Some((pointer, location.location()?)))
+    {
+        let Some((camera_entity, camera, camera_transform)) =
+            q_cameras.iter().find(|(_, camera, _)| {
+                camera.is_active
+                    && camera
+                        .target
+                        .normalize(primary_window)
+                        .is_some_and(|target| target == location.target)
+            })
+        else {
+            continue;
+        };
+
+        let viewport_position = camera
+            .logical_viewport_rect()
+            .map(|viewport| viewport.min)
+            .unwrap_or_default();
+
+        let Ok(ray) =
+            camera.viewport_to_world(camera_transform, location.position - viewport_position)
+        else {
+            continue;
+        };
+
+        let mut blocked = false;
+        let mut picks = Vec::new();
+
+        for (
+            entity,
+            vector,
+            transform,
+            fill,
+            stroke,
+            fills,
+            strokes,
+            hatch_fill,
+            variable_stroke,
+            clip_path,
+            picking_behavior,
+        ) in vectors.iter().copied()
+        {
+            if blocked {
+                break;
+            }
+
+            // Intersect the ray with the plane of the shape.
+            let world_to_local = transform.affine().inverse();
+            let origin = world_to_local.transform_point3(ray.origin);
+            let direction = world_to_local.transform_vector3(*ray.direction);
+            if direction.z == 0.0 {
+                continue;
+            }
+
+            let distance = -origin.z / direction.z;
+            if distance < 0.0 {
+                continue;
+            }
+
+            let local = origin + direction * distance;
+            let point = kurbo::Affine::FLIP_Y * kurbo::Point::new(local.x as f64, local.y as f64);
+
+            // Clipped away parts of the shape are not visible.
+            if clip_path
+                .and_then(ClipPath::path)
+                .is_some_and(|path| !hit::fill_contains(path, point, peniko::Fill::NonZero))
+            {
+                continue;
+            }
+
+            let shape = vector.shape();
+            let mut fills = fill
+                .into_iter()
+                .chain(fills.iter().flat_map(|fills| &fills.0))
+                .peekable();
+            // The hatch fill is clipped with the fill rule of the first fill.
+            let hatch_fill_rule = fills
+                .peek()
+                .map_or(peniko::Fill::NonZero, |fill| fill.style);
+
+            let hit = hatch_fill
+                .is_some_and(|hatch_fill| hatch_fill.contains(&shape, point, hatch_fill_rule))
+                || fills.any(|fill| hit::fill_contains(&shape, point, fill.style))
+                || stroke
+                    .into_iter()
+                    .chain(strokes.iter().flat_map(|strokes| &strokes.0))
+                    .any(|stroke| stroke.contains(&shape, point))
+                || variable_stroke.is_some_and(|variable_stroke| {
+                    hit::fill_contains(
+                        &variable_stroke.outline(&shape),
+                        point,
+                        peniko::Fill::NonZero,
+                    )
+                });
+
+            if !hit {
+                continue;
+            }
+
+            blocked = picking_behavior.is_none_or(|behavior| behavior.should_block_lower);
+
+            let position = transform.transform_point(local);
+            let depth = (position - ray.origin).dot(*ray.direction);
+            picks.push((
+                entity,
+                HitData::new(
+                    camera_entity,
+                    depth,
+                    Some(position),
+                    Some(*transform.back()),
+                ),
+            ));
+        }
+
+        output.send(PointerHits::new(*pointer, picks, camera.order as f32));
+    }
+}
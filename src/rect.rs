@@ -67,6 +67,16 @@ impl Vector for VelloRect {
         kurbo::RoundedRect::new(self.x0(), self.y0(), self.x1(), self.y1(), self.radius)
     }
 
+    fn rounded_rect(&self) -> Option<kurbo::RoundedRect> {
+        Some(kurbo::RoundedRect::new(
+            self.x0(),
+            self.y0(),
+            self.x1(),
+            self.y1(),
+            self.radius,
+        ))
+    }
+
     fn border_translation(&self, mut time: f64) -> DVec2 {
         // Loop around the rect
         if time > 0.0 {
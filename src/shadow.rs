@@ -0,0 +1,179 @@
+//! Drop shadows and glows beneath [`Vector`] shapes.
+
+use bevy_color::{Alpha, Color};
+use bevy_ecs::prelude::*;
+use bevy_math::DVec2;
+use bevy_utils::prelude::*;
+use bevy_vello::prelude::*;
+use kurbo::Shape;
+
+use crate::{brush::to_peniko_color, offset::offset_path, SceneHolder, Vector};
+
+/// Number of stacked layers used to approximate the blur of arbitrary shapes,
+/// enough to hide the banding between them for common blur radii.
+const SHADOW_STEPS: usize = 8;
+
+/// Draw [`Shadow`]s of [`Vector`] shapes.
+#[allow(clippy::type_complexity)]
+pub(super) fn draw_shadows<V: Vector + Component>(
+    mut commands: Commands,
+    q_shadows: Query<(Entity, &V, &Shadow), Or<(Changed<V>, Changed<Shadow>)>>,
+    q_vectors: Query<(), With<V>>,
+    mut removed_shadows: RemovedComponents<Shadow>,
+) {
+    for (entity, vector, shadow) in q_shadows.iter() {
+        let mut scene = vello::Scene::new();
+        shadow.draw(&mut scene, vector);
+
+        commands
+            .entity(entity)
+            .insert(SceneHolder::<ShadowScene>::new(scene));
+    }
+
+    // Clear the scene so that the entity is composited without its shadow.
+    for entity in removed_shadows.read() {
+        if q_vectors.contains(entity) {
+            commands
+                .entity(entity)
+                .insert(SceneHolder::<ShadowScene>::new(vello::Scene::new()));
+        }
+    }
+}
+
+/// A blurred silhouette drawn beneath a [`Vector`].
+///
+/// Shapes that can be represented as a [`kurbo::RoundedRect`] (see [`Vector::rounded_rect`])
+/// use vello's blurred rounded rect. Vello cannot blur arbitrary paths, so other shapes
+/// approximate the blur by stacking 8 translucent expansions and insets of the shape
+/// in separate layers. Insets are computed with [`offset_path`], which flattens the shape.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Shadow {
+    /// Offset of the shadow in the coordinate space of the shape.
+    pub offset: DVec2,
+    /// Radius of the blur.
+    pub blur_radius: f64,
+    pub color: Color,
+    /// Distance to expand (or shrink, if negative) the shadow before blurring.
+    pub spread: f64,
+}
+
+impl Default for Shadow {
+    fn default() -> Self {
+        Self {
+            offset: DVec2::ZERO,
+            blur_radius: 0.0,
+            color: Color::BLACK.with_alpha(0.5),
+            spread: 0.0,
+        }
+    }
+}
+
+impl Shadow {
+    pub fn new(offset: impl Into<DVec2>, blur_radius: f64) -> Self {
+        Self {
+            offset: offset.into(),
+            blur_radius,
+            ..default()
+        }
+    }
+
+    /// Creates a glow around the shape, which is a shadow without any offset.
+    pub fn glow(blur_radius: f64, color: Color) -> Self {
+        Self::new(DVec2::ZERO, blur_radius).with_color(color)
+    }
+
+    pub fn with_offset(mut self, offset: impl Into<DVec2>) -> Self {
+        self.offset = offset.into();
+        self
+    }
+
+    pub fn with_blur_radius(mut self, blur_radius: f64) -> Self {
+        self.blur_radius = blur_radius;
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn with_spread(mut self, spread: f64) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    /// Draw the shadow of a `vector`.
+    pub(crate) fn draw(&self, scene: &mut vello::Scene, vector: &impl Vector) {
+        let transform = kurbo::Affine::translate((self.offset.x, self.offset.y));
+        let color = to_peniko_color(self.color);
+
+        let rounded_rect = vector
+            .rounded_rect()
+            .and_then(|rect| Some((rect.rect(), rect.radii().as_single_radius()?)));
+
+        if let Some((rect, radius)) = rounded_rect {
+            scene.draw_blurred_rounded_rect(
+                transform,
+                rect.inflate(self.spread, self.spread),
+                color,
+                (radius + self.spread).max(0.0),
+                self.blur_radius * 0.5,
+            );
+            return;
+        }
+
+        let shape = vector.shape();
+        let steps = if self.blur_radius > 0.0 {
+            SHADOW_STEPS
+        } else {
+            1
+        };
+
+        // Opacity of each layer so that fully overlapping layers add up to the color's alpha.
+        let alpha = 1.0 - (1.0 - color.components[3]).powf(1.0 / steps as f32);
+        let color = color.with_alpha(1.0);
+
+        // Layers only need to cover the largest expansion.
+        let extent = (self.spread + self.blur_radius).max(0.0);
+        let bounds = shape.bounding_box().inflate(extent, extent);
+
+        // Expansions range from `spread - blur_radius` to `spread + blur_radius`,
+        // approximating a blurred edge with a linear falloff.
+        for step in 0..steps {
+            let t = (step as f64 + 0.5) / steps as f64;
+            let expansion = self.spread + self.blur_radius * (2.0 * t - 1.0);
+
+            // Each expansion is drawn in its own layer, so that the overlapping fill and
+            // stroke of one expansion are only blended once.
+            scene.push_layer(peniko::Mix::Normal, alpha, transform, &bounds);
+            if expansion < 0.0 {
+                let inset = offset_path(&shape, expansion, kurbo::Join::Round);
+                scene.fill(peniko::Fill::NonZero, transform, color, None, &inset);
+            } else {
+                scene.fill(peniko::Fill::NonZero, transform, color, None, &shape);
+            }
+            if expansion > 0.0 {
+                scene.stroke(
+                    &kurbo::Stroke::new(expansion * 2.0).with_join(kurbo::Join::Round),
+                    transform,
+                    color,
+                    None,
+                    &shape,
+                );
+            }
+            scene.pop_layer();
+        }
+    }
+}
+
+/// Marker struct of a shadow scene for [`SceneHolder`].
+pub struct ShadowScene;
+
+impl ShadowScene {
+    /// Default order of the layer in [`SceneLayers`][SceneLayers],
+    /// beneath the [`VectorScene`][VectorScene].
+    ///
+    /// [SceneLayers]: crate::layer::SceneLayers
+    /// [VectorScene]: crate::vector::VectorScene
+    pub const ORDER: i32 = -100;
+}
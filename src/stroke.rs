@@ -2,10 +2,46 @@
 
 use bevy_color::Color;
 use bevy_ecs::prelude::*;
+use bevy_time::prelude::*;
 use bevy_utils::prelude::*;
 use bevy_vello::prelude::*;
+use kurbo::Shape;
 
-use crate::brush::Brush;
+use crate::{
+    boolean::{boolean_path, BooleanOp},
+    brush::{Brush, BrushHolder},
+    hit,
+    vector::TOLERANCE,
+};
+
+/// Scroll the dash offset of [`Stroke`]s and [`Strokes`] with a [`DashAnimation`].
+pub(super) fn animate_dashes(
+    mut q_strokes: Query<(&DashAnimation, Option<&mut Stroke>, Option<&mut Strokes>)>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_secs_f64();
+    if delta == 0.0 {
+        return;
+    }
+
+    for (animation, stroke, strokes) in q_strokes.iter_mut() {
+        let distance = animation.speed * delta;
+
+        // Check through a read-only reference first, so that change detection only
+        // fires for strokes that actually have dashes to scroll.
+        if let Some(mut stroke) = stroke.filter(|stroke| stroke.has_dashes()) {
+            stroke.scroll_dashes(distance);
+        }
+
+        if let Some(mut strokes) =
+            strokes.filter(|strokes| strokes.0.iter().any(Stroke::has_dashes))
+        {
+            for stroke in strokes.0.iter_mut().filter(|stroke| stroke.has_dashes()) {
+                stroke.scroll_dashes(distance);
+            }
+        }
+    }
+}
 
 /// Stroke of a [`Vector`][Vector].
 ///
@@ -14,6 +50,7 @@ use crate::brush::Brush;
 pub struct Stroke {
     pub style: kurbo::Stroke,
     pub brush: Brush,
+    pub alignment: StrokeAlignment,
 }
 
 impl Stroke {
@@ -42,4 +79,313 @@ impl Stroke {
         self.style = style;
         self
     }
+
+    pub fn with_alignment(mut self, alignment: StrokeAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Sets the dash `pattern` (alternating dash and gap lengths) and its starting `offset`.
+    pub fn with_dashes(mut self, pattern: impl IntoIterator<Item = f64>, offset: f64) -> Self {
+        self.style = self.style.with_dashes(offset, pattern);
+        self
+    }
+
+    /// Returns true if the stroke has a dash pattern with a non-zero length.
+    fn has_dashes(&self) -> bool {
+        self.style.dash_pattern.iter().sum::<f64>() > 0.0
+    }
+
+    /// Moves the dashes forward along the outline by `distance`.
+    fn scroll_dashes(&mut self, distance: f64) {
+        let period = self.style.dash_pattern.iter().sum::<f64>();
+        if period <= 0.0 {
+            return;
+        }
+
+        // Wrap around the period to keep the offset precise over time.
+        self.style.dash_offset = (self.style.dash_offset - distance).rem_euclid(period);
+    }
+
+    /// Maximum distance of the stroke outline from the outline of a `shape`,
+    /// accounting for the [`StrokeAlignment`], miter joins and square caps.
+    pub fn extent(&self, shape: &impl kurbo::Shape) -> f64 {
+        let half_width = self.style.width * 0.5;
+
+        let join = match self.style.join {
+            kurbo::Join::Miter => self.style.miter_limit.max(1.0),
+            kurbo::Join::Bevel | kurbo::Join::Round => 1.0,
+        };
+        let cap = if self.style.start_cap == kurbo::Cap::Square
+            || self.style.end_cap == kurbo::Cap::Square
+        {
+            std::f64::consts::SQRT_2
+        } else {
+            1.0
+        };
+
+        let extent = half_width * join.max(cap);
+        if self.alignment == StrokeAlignment::Outside && is_closed(shape) {
+            extent * 2.0
+        } else {
+            extent
+        }
+    }
+
+    /// Returns true if the `point` is within the stroke drawn along the outline of a `shape`.
+    ///
+    /// Like the drawn stroke, inside and outside strokes of closed shapes cover the full width
+    /// on one side of the outline. Joins, caps and dashes are not taken into account,
+    /// treating the stroke as a continuous line with round joins and caps, so the tips of
+    /// miter joins and square caps are not contained.
+    pub fn contains(&self, shape: &impl kurbo::Shape, point: kurbo::Point) -> bool {
+        let distance = hit::distance_to_outline(shape, point);
+        let width = self.style.width;
+
+        if self.alignment == StrokeAlignment::Center || !is_closed(shape) {
+            return distance <= width * 0.5;
+        }
+
+        let inside = hit::fill_contains(shape, point, peniko::Fill::NonZero);
+        distance <= width && inside == (self.alignment == StrokeAlignment::Inside)
+    }
+
+    /// Expands the stroke drawn along the outline of a `shape` into a path
+    /// to be filled with [`peniko::Fill::NonZero`].
+    ///
+    /// Joins, caps, dashes and the [`StrokeAlignment`] are all taken into account.
+    pub fn outline(&self, shape: &impl kurbo::Shape) -> kurbo::BezPath {
+        let expand = |style: &kurbo::Stroke| {
+            kurbo::stroke(
+                shape.path_elements(TOLERANCE),
+                style,
+                &kurbo::StrokeOpts::default(),
+                TOLERANCE,
+            )
+        };
+
+        if self.alignment == StrokeAlignment::Center || !is_closed(shape) {
+            return expand(&self.style);
+        }
+
+        // Only half of the stroke remains after the boolean operation.
+        let mut style = self.style.clone();
+        style.width *= 2.0;
+
+        let op = match self.alignment {
+            StrokeAlignment::Inside => BooleanOp::Intersect,
+            _ => BooleanOp::Difference,
+        };
+        boolean_path(&expand(&style), shape, op)
+    }
+
+    /// Draw the stroke of a `shape` based on its [`StrokeAlignment`].
+    pub(crate) fn draw(
+        &self,
+        scene: &mut vello::Scene,
+        transform: kurbo::Affine,
+        shape: &impl kurbo::Shape,
+    ) {
+        let draw_stroke = |scene: &mut vello::Scene, style: &kurbo::Stroke| {
+            scene.stroke(
+                style,
+                transform,
+                &self.brush.value,
+                Some(self.brush.resolve_transform(shape)),
+                shape,
+            );
+        };
+
+        if self.alignment == StrokeAlignment::Center || !is_closed(shape) {
+            draw_stroke(scene, &self.style);
+            return;
+        }
+
+        let path = shape.to_path(TOLERANCE);
+        let area = path.area();
+
+        let clip = if self.alignment == StrokeAlignment::Inside {
+            path
+        } else {
+            // Wind the shape against bounds covering the whole stroke,
+            // so that its inside is excluded.
+            let extent = self.extent(shape);
+            let mut clip = path
+                .bounding_box()
+                .inflate(extent, extent)
+                .to_path(TOLERANCE);
+            if clip.area().signum() == area.signum() {
+                clip.extend(path.reverse_subpaths());
+            } else {
+                clip.extend(path);
+            }
+            clip
+        };
+
+        // Only half of the stroke remains visible after clipping.
+        let mut style = self.style.clone();
+        style.width *= 2.0;
+
+        scene.push_layer(peniko::Mix::Clip, 1.0, transform, &clip);
+        draw_stroke(scene, &style);
+        scene.pop_layer();
+    }
+}
+
+impl BrushHolder for Stroke {
+    fn brushes(&self) -> impl Iterator<Item = &Brush> {
+        std::iter::once(&self.brush)
+    }
+
+    fn brushes_mut(&mut self) -> impl Iterator<Item = &mut Brush> {
+        std::iter::once(&mut self.brush)
+    }
+}
+
+/// Alignment of a [`Stroke`] relative to the outline of a closed shape.
+///
+/// Open shapes are always stroked at the center.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrokeAlignment {
+    /// The stroke is centered on the outline.
+    #[default]
+    Center,
+    /// The stroke is drawn entirely inside of the outline.
+    Inside,
+    /// The stroke is drawn entirely outside of the outline.
+    Outside,
+}
+
+/// Returns true if every subpath of a `shape` is closed with a [`kurbo::PathEl::ClosePath`].
+///
+/// Only closed shapes have an inside and outside for [`StrokeAlignment`].
+pub fn is_closed(shape: &impl kurbo::Shape) -> bool {
+    let mut is_empty = true;
+    let mut is_open = false;
+
+    for el in shape.path_elements(TOLERANCE) {
+        match el {
+            kurbo::PathEl::MoveTo(_) if is_open => return false,
+            kurbo::PathEl::ClosePath => is_open = false,
+            _ => is_open = true,
+        }
+        is_empty = false;
+    }
+
+    !is_empty && !is_open
+}
+
+/// Multiple [`Stroke`]s of a [`Vector`][Vector], drawn in order on top of each other.
+///
+/// If a [`Stroke`] is also present, it is drawn first.
+///
+/// [Vector]: crate::Vector
+#[derive(Component, Default, Debug, Clone)]
+pub struct Strokes(pub Vec<Stroke>);
+
+impl Strokes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_stroke(mut self, stroke: Stroke) -> Self {
+        self.0.push(stroke);
+        self
+    }
+}
+
+impl BrushHolder for Strokes {
+    fn brushes(&self) -> impl Iterator<Item = &Brush> {
+        self.0.iter().map(|stroke| &stroke.brush)
+    }
+
+    fn brushes_mut(&mut self) -> impl Iterator<Item = &mut Brush> {
+        self.0.iter_mut().map(|stroke| &mut stroke.brush)
+    }
+}
+
+/// Scrolls the dash offset of the [`Stroke`] and [`Strokes`] of an entity over time,
+/// e.g. for marching ants selection outlines or flow animations.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct DashAnimation {
+    /// Distance travelled by the dashes along the outline per second.
+    ///
+    /// Positive values move the dashes forward along the outline.
+    pub speed: f64,
+}
+
+impl DashAnimation {
+    pub fn new(speed: f64) -> Self {
+        Self { speed }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contains(alignment: StrokeAlignment, shape: &impl kurbo::Shape, x: f64, y: f64) -> bool {
+        Stroke::new(2.0)
+            .with_alignment(alignment)
+            .contains(shape, kurbo::Point::new(x, y))
+    }
+
+    const RECT: kurbo::Rect = kurbo::Rect::new(0.0, 0.0, 10.0, 10.0);
+
+    #[test]
+    fn center_contains() {
+        let alignment = StrokeAlignment::Center;
+
+        assert!(contains(alignment, &RECT, 5.0, 0.9));
+        assert!(contains(alignment, &RECT, 5.0, -0.9));
+        assert!(!contains(alignment, &RECT, 5.0, 1.1));
+        assert!(!contains(alignment, &RECT, 5.0, -1.1));
+    }
+
+    #[test]
+    fn inside_contains() {
+        let alignment = StrokeAlignment::Inside;
+
+        assert!(contains(alignment, &RECT, 5.0, 1.9));
+        assert!(!contains(alignment, &RECT, 5.0, 2.1));
+        assert!(!contains(alignment, &RECT, 5.0, -0.5));
+        // Inner corners are sharp, both when drawn and when tested.
+        assert!(contains(alignment, &RECT, 1.9, 1.9));
+        assert!(!contains(alignment, &RECT, 2.1, 2.1));
+    }
+
+    #[test]
+    fn outside_contains() {
+        let alignment = StrokeAlignment::Outside;
+
+        assert!(contains(alignment, &RECT, 5.0, -1.9));
+        assert!(!contains(alignment, &RECT, 5.0, -2.1));
+        assert!(!contains(alignment, &RECT, 5.0, 0.5));
+        // Outer corners are tested as round joins, excluding the tip of the miter.
+        assert!(contains(alignment, &RECT, -1.4, -1.4));
+        assert!(!contains(alignment, &RECT, -1.9, -1.9));
+    }
+
+    #[test]
+    fn open_shapes_contain_centered() {
+        let line = kurbo::Line::new((0.0, 0.0), (10.0, 0.0));
+
+        for alignment in [
+            StrokeAlignment::Center,
+            StrokeAlignment::Inside,
+            StrokeAlignment::Outside,
+        ] {
+            assert!(contains(alignment, &line, 5.0, 0.9));
+            assert!(contains(alignment, &line, 5.0, -0.9));
+            assert!(!contains(alignment, &line, 5.0, 1.1));
+        }
+    }
+
+    #[test]
+    fn closed_shapes() {
+        assert!(is_closed(&RECT));
+        assert!(is_closed(&kurbo::Circle::new((0.0, 0.0), 1.0)));
+        assert!(!is_closed(&kurbo::Line::new((0.0, 0.0), (1.0, 0.0))));
+        assert!(!is_closed(&kurbo::BezPath::new()));
+    }
 }
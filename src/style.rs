@@ -0,0 +1,260 @@
+//! Named [`Fill`] and [`Stroke`] styles shared between entities.
+
+use std::borrow::Cow;
+
+use bevy_ecs::{prelude::*, system::EntityCommands};
+use bevy_utils::HashMap;
+
+use crate::{
+    fill::Fill,
+    head::{HeadFill, HeadStroke},
+    stroke::Stroke,
+};
+
+/// Insert the [`VelloStyle`] referenced by [`StyleKey`]s into their entities.
+///
+/// Entities are only updated when the revision of their style changes, e.g. when it is
+/// mutably accessed or when their key changes. Components supplied by the previous style that
+/// are no longer supplied (including when the [`StyleKey`] is removed) are removed.
+pub(super) fn apply_styles(
+    mut commands: Commands,
+    styles: Res<VelloStyles>,
+    q_keys: Query<(Entity, Ref<StyleKey>, Option<&AppliedStyle>)>,
+    q_applied: Query<&AppliedStyle, Without<StyleKey>>,
+    mut removed_keys: RemovedComponents<StyleKey>,
+) {
+    for entity in removed_keys.read() {
+        if let Ok(applied) = q_applied.get(entity) {
+            let mut entity = commands.entity(entity);
+            applied.remove_unsupplied(&mut entity, &AppliedStyle::default());
+            entity.remove::<AppliedStyle>();
+        }
+    }
+
+    for (entity, key, applied) in q_keys.iter() {
+        if !styles.is_changed() && !key.is_changed() {
+            continue;
+        }
+
+        let style = styles.0.get(&key.0);
+        let revision = style.map(|style| style.revision);
+        if applied.is_some_and(|applied| applied.revision == revision) {
+            continue;
+        }
+
+        let style = style.map(|style| &style.style);
+        let supplied = AppliedStyle {
+            revision,
+            fill: style.is_some_and(|style| style.fill.is_some()),
+            stroke: style.is_some_and(|style| style.stroke.is_some()),
+            head_fill: style.is_some_and(|style| style.head_fill.is_some()),
+            head_stroke: style.is_some_and(|style| style.head_stroke.is_some()),
+        };
+
+        let mut entity = commands.entity(entity);
+        if let Some(applied) = applied {
+            applied.remove_unsupplied(&mut entity, &supplied);
+        }
+
+        if let Some(style) = style {
+            if let Some(fill) = &style.fill {
+                entity.insert(fill.clone());
+            }
+            if let Some(stroke) = &style.stroke {
+                entity.insert(stroke.clone());
+            }
+            if let Some(head_fill) = &style.head_fill {
+                entity.insert(HeadFill(head_fill.clone()));
+            }
+            if let Some(head_stroke) = &style.head_stroke {
+                entity.insert(HeadStroke(head_stroke.clone()));
+            }
+        }
+
+        entity.insert(supplied);
+    }
+}
+
+/// A set of fills and strokes that can be shared between entities through a [`StyleKey`].
+///
+/// Components that are not specified by the style are left untouched on the entities,
+/// unless they were supplied by a previous revision of the style or a previous style.
+#[derive(Default, Debug, Clone)]
+pub struct VelloStyle {
+    pub fill: Option<Fill>,
+    pub stroke: Option<Stroke>,
+    pub head_fill: Option<Fill>,
+    pub head_stroke: Option<Stroke>,
+}
+
+impl VelloStyle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_fill(mut self, fill: Fill) -> Self {
+        self.fill = Some(fill);
+        self
+    }
+
+    pub fn with_stroke(mut self, stroke: Stroke) -> Self {
+        self.stroke = Some(stroke);
+        self
+    }
+
+    pub fn with_head_fill(mut self, fill: Fill) -> Self {
+        self.head_fill = Some(fill);
+        self
+    }
+
+    pub fn with_head_stroke(mut self, stroke: Stroke) -> Self {
+        self.head_stroke = Some(stroke);
+        self
+    }
+}
+
+/// Named [`VelloStyle`]s referenced by [`StyleKey`]s.
+///
+/// Inserting or mutably accessing a style updates every entity that references it,
+/// while entities referencing other styles are left untouched.
+#[derive(Resource, Default, Debug, Clone)]
+pub struct VelloStyles(HashMap<Cow<'static, str>, RevisedStyle>, u64);
+
+impl VelloStyles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_style(mut self, name: impl Into<Cow<'static, str>>, style: VelloStyle) -> Self {
+        self.insert(name, style);
+        self
+    }
+
+    /// Inserts a style, returning the previous style with the same name if any.
+    pub fn insert(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        style: VelloStyle,
+    ) -> Option<VelloStyle> {
+        let revision = self.next_revision();
+        self.0
+            .insert(name.into(), RevisedStyle { style, revision })
+            .map(|previous| previous.style)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&VelloStyle> {
+        self.0.get(name).map(|style| &style.style)
+    }
+
+    /// Returns a style to be modified, which updates every entity that references it.
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut VelloStyle> {
+        let revision = self.next_revision();
+        self.0.get_mut(name).map(|style| {
+            style.revision = revision;
+            &mut style.style
+        })
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<VelloStyle> {
+        self.0.remove(name).map(|style| style.style)
+    }
+
+    fn next_revision(&mut self) -> u64 {
+        self.1 += 1;
+        self.1
+    }
+}
+
+/// A [`VelloStyle`] with a revision that is unique across all styles in [`VelloStyles`].
+#[derive(Debug, Clone)]
+struct RevisedStyle {
+    style: VelloStyle,
+    revision: u64,
+}
+
+/// Revision of the style last applied to an entity, and the components it supplied.
+#[derive(Component, Default, Debug, Clone, Copy)]
+pub(crate) struct AppliedStyle {
+    revision: Option<u64>,
+    fill: bool,
+    stroke: bool,
+    head_fill: bool,
+    head_stroke: bool,
+}
+
+impl AppliedStyle {
+    /// Remove the components supplied by this style that are not `supplied` anymore.
+    fn remove_unsupplied(&self, entity: &mut EntityCommands, supplied: &AppliedStyle) {
+        if self.fill && !supplied.fill {
+            entity.remove::<Fill>();
+        }
+        if self.stroke && !supplied.stroke {
+            entity.remove::<Stroke>();
+        }
+        if self.head_fill && !supplied.head_fill {
+            entity.remove::<HeadFill>();
+        }
+        if self.head_stroke && !supplied.head_stroke {
+            entity.remove::<HeadStroke>();
+        }
+    }
+}
+
+/// Name of the [`VelloStyle`] in [`VelloStyles`] to be applied to the entity.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StyleKey(pub Cow<'static, str>);
+
+impl StyleKey {
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        Self(name.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::prelude::*;
+
+    use super::*;
+    use crate::{
+        rect::VelloRect,
+        vector::{draw_vectors, VectorScene},
+        SceneHolder,
+    };
+
+    #[test]
+    fn revised_style_redraws_removed_stroke() {
+        let mut app = App::new();
+        app.init_resource::<VelloStyles>()
+            .add_systems(Update, (apply_styles, draw_vectors::<VelloRect>).chain());
+
+        app.world_mut()
+            .resource_mut::<VelloStyles>()
+            .insert("a", VelloStyle::new().with_stroke(Stroke::new(2.0)));
+        let entity = app
+            .world_mut()
+            .spawn((VelloRect::new(10.0, 10.0), Fill::new(), StyleKey::new("a")))
+            .id();
+        app.update();
+        assert!(app.world().get::<Stroke>(entity).is_some());
+
+        let drawn = |app: &App| {
+            app.world()
+                .entity(entity)
+                .get_ref::<SceneHolder<VectorScene>>()
+                .unwrap()
+                .last_changed()
+        };
+        let first = drawn(&app);
+
+        app.world_mut()
+            .resource_mut::<VelloStyles>()
+            .get_mut("a")
+            .unwrap()
+            .stroke = None;
+        app.update();
+
+        assert!(app.world().get::<Stroke>(entity).is_none());
+        assert!(app.world().get::<Fill>(entity).is_some());
+        assert_ne!(drawn(&app), first);
+    }
+}
@@ -0,0 +1,406 @@
+//! Strokes with a varying width along the outline of a [`Vector`][Vector] shape.
+//!
+//! [Vector]: crate::Vector
+
+use bevy_color::Color;
+use bevy_ecs::prelude::*;
+use bevy_utils::prelude::*;
+use bevy_vello::prelude::*;
+
+use crate::{
+    brush::{Brush, BrushHolder},
+    vector::TOLERANCE,
+};
+
+/// Maximum length of a miter relative to the width, to prevent spikes at sharp corners.
+const MITER_LIMIT: f64 = 4.0;
+
+/// A stroke with a varying width along the outline of a [`Vector`][Vector].
+///
+/// The outline is expanded into a filled path, with the width at each point sampled from
+/// the [`WidthProfile`] by its position along the normalized length of the outline.
+/// For a traced [`VelloBezPath`][VelloBezPath], only the traced part of the path is stroked,
+/// so a taper moves along with the tip of the path.
+///
+/// [Vector]: crate::Vector
+/// [VelloBezPath]: crate::bezpath::VelloBezPath
+#[derive(Component, Default, Debug, Clone)]
+pub struct VariableStroke {
+    pub profile: WidthProfile,
+    pub brush: Brush,
+}
+
+impl VariableStroke {
+    pub fn new(profile: WidthProfile) -> Self {
+        Self {
+            profile,
+            ..default()
+        }
+    }
+
+    pub fn with_brush(mut self, brush: Brush) -> Self {
+        self.brush = brush;
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.brush = Brush::from_color(color);
+        self
+    }
+
+    pub fn with_profile(mut self, profile: WidthProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Maximum distance of the expanded outline from the outline of the shape.
+    pub fn extent(&self) -> f64 {
+        let max_width = self
+            .profile
+            .points()
+            .iter()
+            .fold(0.0, |max, (_, width)| f64::max(max, *width));
+
+        max_width * 0.5 * MITER_LIMIT
+    }
+
+    /// Draw the expanded outline of a `shape`.
+    pub(crate) fn draw(
+        &self,
+        scene: &mut vello::Scene,
+        transform: kurbo::Affine,
+        shape: &impl kurbo::Shape,
+    ) {
+        scene.fill(
+            peniko::Fill::NonZero,
+            transform,
+            &self.brush.value,
+            Some(self.brush.resolve_transform(shape)),
+            &self.outline(shape),
+        );
+    }
+
+    /// Expands the outline of a `shape` into a path to be filled with [`peniko::Fill::NonZero`].
+    pub fn outline(&self, shape: &impl kurbo::Shape) -> kurbo::BezPath {
+        let polylines = flatten_polylines(shape);
+        let total_length = polylines.iter().map(Polyline::length).sum::<f64>();
+
+        let mut path = kurbo::BezPath::new();
+        if total_length <= 0.0 {
+            return path;
+        }
+
+        let mut distance = 0.0;
+        for polyline in polylines.iter() {
+            let points = &polyline.points;
+            let count = points.len();
+            if count < 2 {
+                continue;
+            }
+
+            let mut left = Vec::with_capacity(count + 1);
+            let mut right = Vec::with_capacity(count + 1);
+
+            // Closed polylines revisit their first point at the end.
+            let indices = (0..count).chain(polyline.closed.then_some(0));
+            for (i, index) in indices.enumerate() {
+                if i > 0 {
+                    let prev_index = if index == 0 { count - 1 } else { index - 1 };
+                    distance += (points[index] - points[prev_index]).hypot();
+                }
+
+                let normal = polyline.normal(index);
+                let half_width = self.profile.width_at(distance / total_length) * 0.5;
+
+                left.push(points[index] + normal * half_width);
+                right.push(points[index] - normal * half_width);
+            }
+
+            if polyline.closed {
+                // Two loops of opposite windings form a ring.
+                push_polygon(&mut path, left.iter().copied());
+                push_polygon(&mut path, right.iter().rev().copied());
+            } else {
+                push_polygon(&mut path, left.iter().chain(right.iter().rev()).copied());
+            }
+        }
+
+        path
+    }
+}
+
+impl BrushHolder for VariableStroke {
+    fn brushes(&self) -> impl Iterator<Item = &Brush> {
+        std::iter::once(&self.brush)
+    }
+
+    fn brushes_mut(&mut self) -> impl Iterator<Item = &mut Brush> {
+        std::iter::once(&mut self.brush)
+    }
+}
+
+/// Widths along the normalized length `[0.0, 1.0]` of an outline.
+///
+/// Widths are linearly interpolated between the points of the profile,
+/// and clamped before the first and after the last point.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WidthProfile(Vec<(f64, f64)>);
+
+impl Default for WidthProfile {
+    fn default() -> Self {
+        Self::constant(1.0)
+    }
+}
+
+impl WidthProfile {
+    /// Creates a profile from `(position, width)` points.
+    pub fn new(points: impl IntoIterator<Item = (f64, f64)>) -> Self {
+        let mut points = points.into_iter().collect::<Vec<_>>();
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self(points)
+    }
+
+    /// Creates a profile with the same width along the entire outline.
+    pub fn constant(width: f64) -> Self {
+        Self(vec![(0.0, width)])
+    }
+
+    /// Creates a profile that linearly changes from the `start` to the `end` width.
+    pub fn taper(start: f64, end: f64) -> Self {
+        Self(vec![(0.0, start), (1.0, end)])
+    }
+
+    /// Returns the `(position, width)` points of the profile.
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.0
+    }
+
+    /// Returns the width at a `position` along the normalized length of an outline.
+    pub fn width_at(&self, position: f64) -> f64 {
+        let index = self.0.partition_point(|(p, _)| *p <= position);
+
+        match (index.checked_sub(1).map(|i| self.0[i]), self.0.get(index)) {
+            (Some((p0, w0)), Some(&(p1, w1))) => {
+                let t = (position - p0) / (p1 - p0);
+                w0 + (w1 - w0) * t
+            }
+            (Some((_, w)), None) | (None, Some(&(_, w))) => w,
+            (None, None) => 0.0,
+        }
+    }
+}
+
+/// Push a closed polygon into `path`.
+fn push_polygon(path: &mut kurbo::BezPath, mut points: impl Iterator<Item = kurbo::Point>) {
+    let Some(first) = points.next() else {
+        return;
+    };
+
+    path.move_to(first);
+    for point in points {
+        path.line_to(point);
+    }
+    path.close_path();
+}
+
+/// A flattened subpath of a shape.
+struct Polyline {
+    points: Vec<kurbo::Point>,
+    closed: bool,
+}
+
+impl Polyline {
+    fn length(&self) -> f64 {
+        let length = self
+            .points
+            .windows(2)
+            .map(|w| (w[1] - w[0]).hypot())
+            .sum::<f64>();
+
+        match (self.closed, self.points.first(), self.points.last()) {
+            (true, Some(first), Some(last)) => length + (*first - *last).hypot(),
+            _ => length,
+        }
+    }
+
+    /// Unit normal of the edge going from the point at `index` to the next point.
+    fn edge_normal(&self, index: usize) -> Option<kurbo::Vec2> {
+        let count = self.points.len();
+        let next = if index + 1 < count {
+            index + 1
+        } else if self.closed {
+            0
+        } else {
+            return None;
+        };
+
+        let tangent = (self.points[next] - self.points[index]).normalize();
+        Some(kurbo::Vec2::new(-tangent.y, tangent.x))
+    }
+
+    /// Mitered unit normal at the point at `index`.
+    fn normal(&self, index: usize) -> kurbo::Vec2 {
+        let count = self.points.len();
+        let prev_index = match index {
+            0 if self.closed => Some(count - 1),
+            0 => None,
+            _ => Some(index - 1),
+        };
+
+        let prev = prev_index.and_then(|i| self.edge_normal(i));
+        let next = self.edge_normal(index);
+
+        match (prev, next) {
+            (Some(prev), Some(next)) => {
+                let normal = (prev + next).normalize();
+                let cos = normal.dot(next);
+                if normal.is_finite() && cos > 1.0 / MITER_LIMIT {
+                    normal / cos
+                } else {
+                    next
+                }
+            }
+            (Some(normal), None) | (None, Some(normal)) => normal,
+            (None, None) => kurbo::Vec2::ZERO,
+        }
+    }
+}
+
+/// Flatten a shape into polylines, removing repeated points.
+fn flatten_polylines(shape: &impl kurbo::Shape) -> Vec<Polyline> {
+    let mut polylines = Vec::<Polyline>::new();
+
+    kurbo::flatten(shape.path_elements(TOLERANCE), TOLERANCE, |el| match el {
+        kurbo::PathEl::MoveTo(p) => polylines.push(Polyline {
+            points: vec![p],
+            closed: false,
+        }),
+        kurbo::PathEl::LineTo(p) => {
+            if let Some(polyline) = polylines.last_mut() {
+                if polyline.points.last() != Some(&p) {
+                    polyline.points.push(p);
+                }
+            }
+        }
+        kurbo::PathEl::ClosePath => {
+            if let Some(polyline) = polylines.last_mut() {
+                if polyline.points.len() > 1 && polyline.points.first() == polyline.points.last() {
+                    polyline.points.pop();
+                }
+                polyline.closed = true;
+            }
+        }
+        _ => {}
+    });
+
+    polylines
+}
+
+#[cfg(test)]
+mod tests {
+    use kurbo::Shape;
+
+    use super::*;
+    use crate::hit::fill_contains;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{a} != {b}");
+    }
+
+    #[test]
+    fn width_at_endpoints() {
+        let profile = WidthProfile::taper(2.0, 4.0);
+        assert_close(profile.width_at(0.0), 2.0);
+        assert_close(profile.width_at(0.5), 3.0);
+        assert_close(profile.width_at(1.0), 4.0);
+    }
+
+    #[test]
+    fn width_at_is_clamped() {
+        let profile = WidthProfile::new([(0.75, 4.0), (0.25, 2.0)]);
+        assert_close(profile.width_at(-1.0), 2.0);
+        assert_close(profile.width_at(0.0), 2.0);
+        assert_close(profile.width_at(0.5), 3.0);
+        assert_close(profile.width_at(1.0), 4.0);
+        assert_close(profile.width_at(2.0), 4.0);
+    }
+
+    #[test]
+    fn width_at_single_point() {
+        let profile = WidthProfile::new([(0.5, 5.0)]);
+        for position in [0.0, 0.5, 1.0] {
+            assert_close(profile.width_at(position), 5.0);
+        }
+
+        assert_close(WidthProfile::constant(3.0).width_at(0.7), 3.0);
+        assert_close(WidthProfile::new([]).width_at(0.5), 0.0);
+    }
+
+    #[test]
+    fn open_path_outline() {
+        let line = kurbo::Line::new((0.0, 0.0), (10.0, 0.0));
+        let outline = VariableStroke::new(WidthProfile::taper(0.0, 4.0)).outline(&line);
+
+        assert_eq!(
+            outline
+                .elements()
+                .iter()
+                .filter(|el| matches!(el, kurbo::PathEl::MoveTo(_)))
+                .count(),
+            1
+        );
+        assert_eq!(
+            outline.bounding_box(),
+            kurbo::Rect::new(0.0, -2.0, 10.0, 2.0)
+        );
+        // Half widths of 0.2 and 1.8 near the start and end.
+        assert!(!fill_contains(
+            &outline,
+            (1.0, 0.5).into(),
+            peniko::Fill::NonZero
+        ));
+        assert!(fill_contains(
+            &outline,
+            (9.0, 1.5).into(),
+            peniko::Fill::NonZero
+        ));
+        assert!(fill_contains(
+            &outline,
+            (9.0, -1.5).into(),
+            peniko::Fill::NonZero
+        ));
+    }
+
+    #[test]
+    fn closed_path_outline() {
+        let rect = kurbo::Rect::new(0.0, 0.0, 10.0, 10.0);
+        let outline = VariableStroke::new(WidthProfile::constant(2.0)).outline(&rect);
+
+        // A ring between the mitered outer and inner squares.
+        assert_close(outline.area().abs(), 12.0 * 12.0 - 8.0 * 8.0);
+        assert_eq!(
+            outline.bounding_box(),
+            kurbo::Rect::new(-1.0, -1.0, 11.0, 11.0)
+        );
+        assert!(fill_contains(
+            &outline,
+            (5.0, 0.5).into(),
+            peniko::Fill::NonZero
+        ));
+        assert!(fill_contains(
+            &outline,
+            (-0.9, -0.9).into(),
+            peniko::Fill::NonZero
+        ));
+        assert!(!fill_contains(
+            &outline,
+            (5.0, 1.5).into(),
+            peniko::Fill::NonZero
+        ));
+        assert!(!fill_contains(
+            &outline,
+            (5.0, 5.0).into(),
+            peniko::Fill::NonZero
+        ));
+    }
+}
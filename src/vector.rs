@@ -1,56 +1,242 @@
 //! Drawing [`Vector`] shapes.
 
-use bevy_ecs::prelude::*;
+use bevy_ecs::{entity::EntityHashSet, prelude::*};
 use bevy_math::DVec2;
-use bevy_vello::vello::{self, kurbo};
+use bevy_vello::vello::{self, kurbo, peniko};
+use kurbo::Shape;
 
-use crate::{Fill, SceneHolder, Stroke};
+use crate::{
+    bezpath::VelloBezPath,
+    clip::{draw_clipped, ClipPath},
+    fill::Fills,
+    hatch::HatchFill,
+    hit,
+    measure::PathMeasure,
+    paint_order::PaintOrder,
+    stroke::Strokes,
+    variable_stroke::VariableStroke,
+    Fill, SceneHolder, Stroke,
+};
 
 /// Draw [`Vector`] shapes.
-#[allow(clippy::type_complexity)]
+///
+/// Shapes are redrawn when any of their drawing components change or are removed.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub(super) fn draw_vectors<V: Vector + Component>(
     mut commands: Commands,
-    q_vectors: Query<
-        (Entity, &V, Option<&Fill>, Option<&Stroke>),
-        Or<(Changed<V>, Changed<Fill>, Changed<Stroke>)>,
+    q_changed: Query<
+        Entity,
+        (
+            With<V>,
+            Or<(
+                Changed<V>,
+                Changed<Fill>,
+                Changed<Stroke>,
+                Changed<Fills>,
+                Changed<Strokes>,
+                Changed<HatchFill>,
+                Changed<VariableStroke>,
+                Changed<PaintOrder>,
+                Changed<ClipPath>,
+            )>,
+        ),
     >,
+    q_vectors: Query<(
+        &V,
+        Option<&Fill>,
+        Option<&Stroke>,
+        Option<&Fills>,
+        Option<&Strokes>,
+        Option<&HatchFill>,
+        Option<&VariableStroke>,
+        Option<&PaintOrder>,
+        Option<&ClipPath>,
+    )>,
+    mut removed_fill: RemovedComponents<Fill>,
+    mut removed_stroke: RemovedComponents<Stroke>,
+    mut removed_fills: RemovedComponents<Fills>,
+    mut removed_strokes: RemovedComponents<Strokes>,
+    mut removed_hatch_fills: RemovedComponents<HatchFill>,
+    mut removed_variable_strokes: RemovedComponents<VariableStroke>,
+    mut removed_paint_orders: RemovedComponents<PaintOrder>,
+    mut removed_clip_paths: RemovedComponents<ClipPath>,
 ) {
-    for (entity, vector, fill, stroke) in q_vectors.iter() {
+    let entities = q_changed
+        .iter()
+        .chain(removed_fill.read())
+        .chain(removed_stroke.read())
+        .chain(removed_fills.read())
+        .chain(removed_strokes.read())
+        .chain(removed_hatch_fills.read())
+        .chain(removed_variable_strokes.read())
+        .chain(removed_paint_orders.read())
+        .chain(removed_clip_paths.read())
+        .collect::<EntityHashSet>();
+
+    for entity in entities {
+        let Ok((
+            vector,
+            fill,
+            stroke,
+            fills,
+            strokes,
+            hatch_fill,
+            variable_stroke,
+            paint_order,
+            clip_path,
+        )) = q_vectors.get(entity)
+        else {
+            continue;
+        };
+
         let mut scene = vello::Scene::new();
 
-        if let Some(fill) = fill {
+        draw_clipped(&mut scene, clip_path, |scene| {
+            draw_shape(
+                scene,
+                kurbo::Affine::IDENTITY,
+                &vector.shape(),
+                fill.into_iter()
+                    .chain(fills.iter().flat_map(|fills| &fills.0)),
+                hatch_fill,
+                stroke
+                    .into_iter()
+                    .chain(strokes.iter().flat_map(|strokes| &strokes.0)),
+                variable_stroke,
+                paint_order.copied().unwrap_or_default(),
+            );
+        });
+
+        commands
+            .entity(entity)
+            .insert(SceneHolder::<VectorScene>::new(scene));
+    }
+}
+
+/// Draw all `fills` (followed by the `hatch_fill`) and all `strokes` (followed by the
+/// `variable_stroke`) of a `shape` in order, based on the [`PaintOrder`].
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn draw_shape<'a>(
+    scene: &mut vello::Scene,
+    transform: kurbo::Affine,
+    shape: &impl kurbo::Shape,
+    fills: impl Iterator<Item = &'a Fill>,
+    hatch_fill: Option<&HatchFill>,
+    strokes: impl Iterator<Item = &'a Stroke>,
+    variable_stroke: Option<&VariableStroke>,
+    paint_order: PaintOrder,
+) {
+    let draw_fills = |scene: &mut vello::Scene| {
+        let mut fill_rule = None;
+        for fill in fills {
+            fill_rule.get_or_insert(fill.style);
             scene.fill(
                 fill.style,
-                kurbo::Affine::IDENTITY,
+                transform,
                 &fill.brush.value,
-                Some(fill.brush.transform),
-                &vector.shape(),
+                Some(fill.brush.resolve_transform(shape)),
+                shape,
             );
         }
 
-        if let Some(stroke) = stroke {
-            scene.stroke(
-                &stroke.style,
-                kurbo::Affine::IDENTITY,
-                &stroke.brush.value,
-                Some(stroke.brush.transform),
-                &vector.shape(),
+        if let Some(hatch_fill) = hatch_fill {
+            hatch_fill.draw(
+                scene,
+                transform,
+                shape,
+                fill_rule.unwrap_or(peniko::Fill::NonZero),
             );
         }
+    };
 
-        commands
-            .entity(entity)
-            .insert(SceneHolder::<VectorScene>::new(scene));
+    let draw_strokes = |scene: &mut vello::Scene| {
+        for stroke in strokes {
+            stroke.draw(scene, transform, shape);
+        }
+
+        if let Some(variable_stroke) = variable_stroke {
+            variable_stroke.draw(scene, transform, shape);
+        }
+    };
+
+    match paint_order {
+        PaintOrder::FillStroke => {
+            draw_fills(scene);
+            draw_strokes(scene);
+        }
+        PaintOrder::StrokeFill => {
+            draw_strokes(scene);
+            draw_fills(scene);
+        }
     }
 }
 
+/// Tolerance used for converting shapes into [`kurbo::BezPath`].
+pub(crate) const TOLERANCE: f64 = 0.1;
+
+/// Converts an `affine` between Bevy's y-up and Vello's y-down coordinates, in either direction.
+///
+/// Points and rects are converted with [`kurbo::Affine::FLIP_Y`] directly.
+pub(crate) fn flip_y(affine: kurbo::Affine) -> kurbo::Affine {
+    kurbo::Affine::FLIP_Y * affine * kurbo::Affine::FLIP_Y
+}
+
 /// Marker struct of a vector scene for [`SceneHolder`].
 pub struct VectorScene;
 
+impl VectorScene {
+    /// Default order of the layer in [`SceneLayers`][SceneLayers].
+    ///
+    /// [SceneLayers]: crate::layer::SceneLayers
+    pub const ORDER: i32 = 0;
+}
+
 pub trait Vector {
     /// Returns vector graphics that implements [`kurbo::Shape`].
     fn shape(&self) -> impl kurbo::Shape;
 
+    /// Returns true if the `point` is inside the shape based on the `fill_rule`.
+    fn contains_point(&self, point: DVec2, fill_rule: peniko::Fill) -> bool {
+        hit::fill_contains(
+            &self.shape(),
+            kurbo::Point::new(point.x, point.y),
+            fill_rule,
+        )
+    }
+
+    /// Returns true if the `point` is within the `stroke` drawn along the outline of the shape.
+    fn stroke_contains_point(&self, point: DVec2, stroke: &Stroke) -> bool {
+        stroke.contains(&self.shape(), kurbo::Point::new(point.x, point.y))
+    }
+
+    /// Expands the `stroke` drawn along the outline of the shape into a filled path.
+    ///
+    /// See [`Stroke::outline`] for more details.
+    fn stroke_outline(&self, stroke: &Stroke) -> VelloBezPath {
+        VelloBezPath::new().with_path(stroke.outline(&self.shape()))
+    }
+
+    /// Measures the outline of the shape.
+    ///
+    /// For a traced [`VelloBezPath`], only the traced part of the path is measured.
+    fn measure(&self) -> PathMeasure {
+        PathMeasure::new(&self.shape())
+    }
+
+    /// Bounds of the shape, excluding strokes.
+    fn bounds(&self) -> kurbo::Rect {
+        self.shape().bounding_box()
+    }
+
+    /// Returns the shape as a [`kurbo::RoundedRect`] if it can be represented as one.
+    ///
+    /// This allows effects like [`Shadow`][Shadow] to take a faster and more accurate path.
+    ///
+    /// [Shadow]: crate::shadow::Shadow
+    fn rounded_rect(&self) -> Option<kurbo::RoundedRect> {
+        None
+    }
+
     /// Translation of the border at a specific `time` value.
     fn border_translation(&self, _time: f64) -> DVec2 {
         DVec2::ZERO